@@ -98,6 +98,13 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> serde_json::Value {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	// Collateral factor 50%, liquidation threshold 80% and reserve factor 10%, as parts per million.
+	// Borrow rates are per block: 0.0001% plus 0.002% at full utilization.
+	let market = |asset_id: u32| {
+		serde_json::json!([0, asset_id, 500_000, 800_000, { "base_rate": 1, "slope": 20 }, 100_000])
+	};
 	serde_json::json!({
 		"balances": {
 			// Configure endowed accounts with initial balance of 1 << 60.
@@ -113,5 +120,36 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			"key": Some(root_key),
 		},
+		"assets": {
+			// Asset 0 pays liquidity mining rewards; 1 and 2 are lent out in pool 0.
+			"assets": [
+				(0, b"Game Token".to_vec(), b"GAME".to_vec(), 12),
+				(1, b"Game Dollar".to_vec(), b"GUSD".to_vec(), 12),
+				(2, b"Gem".to_vec(), b"GEM".to_vec(), 12),
+			],
+			"balances": endowed_accounts.iter().flat_map(|k| [(1, k.clone(), 1u64 << 50), (2, k.clone(), 1u64 << 50)]).collect::<Vec<_>>(),
+		},
+		"lending": {
+			"markets": [market(1), market(2)],
+			"reserves": [],
+			// Alice supplies GUSD and Bob supplies GEM, so each can borrow the other's asset.
+			"deposits": [(0, alice, 1, 1u64 << 40), (0, bob, 2, 1u64 << 40)],
+		},
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::BuildStorage;
+
+	#[test]
+	fn development_genesis_builds() {
+		development_config().unwrap().build_storage().unwrap();
+	}
+
+	#[test]
+	fn local_testnet_genesis_builds() {
+		local_testnet_config().unwrap().build_storage().unwrap();
+	}
+}
@@ -1,104 +1,212 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
-use frame_system::ensure_signed;
-use sp_std::vec::Vec;
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 use codec::{Decode, Encode};
+use frame_support::dispatch::DispatchResult;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct AssetMetadata {
-    pub name: Vec<u8>,
-    pub symbol: Vec<u8>,
-    pub decimals: u8,
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
 }
+
 pub trait AssetManager {
-    type AssetId;
-    type AccountId;
-    type Balance;
-
-    fn register_asset(asset_id: Self::AssetId, metadata: AssetMetadata) -> dispatch::DispatchResult;
-    fn get_asset(asset_id: Self::AssetId) -> Option<AssetMetadata>;
-    fn mint(asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult;
-    fn burn(asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult;
-}
+	type AssetId;
+	type AccountId;
+	type Balance;
 
-decl_storage! {
-    trait Store for Module<T: Config> as AssetModule {
-        pub Assets get(fn assets): map hasher(blake2_128_concat) T::AssetId => Option<AssetMetadata>;
-        pub Balances get(fn balances): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::Balance;
-    }
+	fn register_asset(asset_id: Self::AssetId, metadata: AssetMetadata) -> DispatchResult;
+	fn get_asset(asset_id: Self::AssetId) -> Option<AssetMetadata>;
+	fn mint(asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> DispatchResult;
+	fn burn(asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> DispatchResult;
+	fn balance(asset_id: Self::AssetId, who: &Self::AccountId) -> Self::Balance;
+	fn transfer(
+		asset_id: Self::AssetId,
+		from: Self::AccountId,
+		to: Self::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
 }
 
-decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
-        fn register_asset(origin, asset_id: T::AssetId, metadata: AssetMetadata) -> dispatch::DispatchResult {
-            let _sender = ensure_signed(origin)?;
-            // Register the asset
-            <Assets<T>>::insert(asset_id, metadata);
-            Self::deposit_event(RawEvent::AssetRegistered(asset_id));
-            Ok(())
-        }
-
-        fn mint(origin, asset_id: T::AssetId, to: T::AccountId, amount: T::Balance) -> dispatch::DispatchResult {
-            let _sender = ensure_signed(origin)?;
-            // Mint the asset
-            let new_balance = Self::balances((asset_id, to.clone())).saturating_add(amount);
-            <Balances<T>>::insert((asset_id, to.clone()), new_balance);
-            Self::deposit_event(RawEvent::AssetMinted(asset_id, to, amount));
-            Ok(())
-        }
-
-        fn burn(origin, asset_id: T::AssetId, from: T::AccountId, amount: T::Balance) -> dispatch::DispatchResult {
-            let _sender = ensure_signed(origin)?;
-            // Burn the asset
-            let current_balance = Self::balances((asset_id, from.clone()));
-            let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
-            <Balances<T>>::insert((asset_id, from.clone()), new_balance);
-            Self::deposit_event(RawEvent::AssetBurned(asset_id, from, amount));
-            Ok(())
-        }
-    }
-}
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Saturating};
 
-decl_event!(
-    pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId, AssetId = <T as Config>::AssetId, Balance = <T as Config>::Balance {
-        AssetRegistered(AssetId),
-        AssetMinted(AssetId, AccountId, Balance),
-        AssetBurned(AssetId, AccountId, Balance),
-    }
-);
-
-decl_error! {
-    pub enum Error for Module<T: Config> {
-        InsufficientBalance,
-    }
-}
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching runtime event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type AssetId: Parameter + Member + MaybeSerializeDeserialize + Default + Copy;
+		type Balance: Parameter + Member + MaybeSerializeDeserialize + AtLeast32BitUnsigned + Default + Copy;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn assets)]
+	pub type Assets<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, AssetMetadata>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn balances)]
+	pub type Balances<T: Config> =
+		StorageMap<_, Blake2_128Concat, (T::AssetId, T::AccountId), T::Balance, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// \[asset_id, name, symbol, decimals\]
+		pub assets: Vec<(T::AssetId, Vec<u8>, Vec<u8>, u8)>,
+		/// \[asset_id, who, amount\]
+		pub balances: Vec<(T::AssetId, T::AccountId, T::Balance)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (asset_id, name, symbol, decimals) in &self.assets {
+				Assets::<T>::insert(
+					asset_id,
+					AssetMetadata { name: name.clone(), symbol: symbol.clone(), decimals: *decimals },
+				);
+			}
+			for (asset_id, who, amount) in &self.balances {
+				assert!(Assets::<T>::contains_key(asset_id), "balance of an unregistered asset");
+				Balances::<T>::insert((*asset_id, who.clone()), amount);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// \[asset_id\]
+		AssetRegistered(T::AssetId),
+		/// \[asset_id, to, amount\]
+		AssetMinted(T::AssetId, T::AccountId, T::Balance),
+		/// \[asset_id, from, amount\]
+		AssetBurned(T::AssetId, T::AccountId, T::Balance),
+		/// \[asset_id, from, to, amount\]
+		AssetTransferred(T::AssetId, T::AccountId, T::AccountId, T::Balance),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		InsufficientBalance,
+		BalanceOverflow,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn register_asset(origin: OriginFor<T>, asset_id: T::AssetId, metadata: AssetMetadata) -> DispatchResult {
+			let _sender = ensure_signed(origin)?;
+			// Register the asset
+			Assets::<T>::insert(asset_id, metadata);
+			Self::deposit_event(Event::AssetRegistered(asset_id));
+			Ok(())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn mint(origin: OriginFor<T>, asset_id: T::AssetId, to: T::AccountId, amount: T::Balance) -> DispatchResult {
+			let _sender = ensure_signed(origin)?;
+			// Mint the asset
+			let new_balance = Self::balances((asset_id, to.clone())).saturating_add(amount);
+			Balances::<T>::insert((asset_id, to.clone()), new_balance);
+			Self::deposit_event(Event::AssetMinted(asset_id, to, amount));
+			Ok(())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn burn(origin: OriginFor<T>, asset_id: T::AssetId, from: T::AccountId, amount: T::Balance) -> DispatchResult {
+			let _sender = ensure_signed(origin)?;
+			// Burn the asset
+			let current_balance = Self::balances((asset_id, from.clone()));
+			let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
+			Balances::<T>::insert((asset_id, from.clone()), new_balance);
+			Self::deposit_event(Event::AssetBurned(asset_id, from, amount));
+			Ok(())
+		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn transfer(origin: OriginFor<T>, asset_id: T::AssetId, to: T::AccountId, amount: T::Balance) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			<Self as AssetManager>::transfer(asset_id, sender.clone(), to.clone(), amount)?;
+			Self::deposit_event(Event::AssetTransferred(asset_id, sender, to, amount));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> AssetManager for Pallet<T> {
+		type AssetId = T::AssetId;
+		type AccountId = T::AccountId;
+		type Balance = T::Balance;
+
+		fn register_asset(asset_id: Self::AssetId, metadata: AssetMetadata) -> DispatchResult {
+			Assets::<T>::insert(asset_id, metadata);
+			Ok(())
+		}
+
+		fn get_asset(asset_id: Self::AssetId) -> Option<AssetMetadata> {
+			Assets::<T>::get(asset_id)
+		}
+
+		fn mint(asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> DispatchResult {
+			let new_balance = Self::balances((asset_id, to.clone())).saturating_add(amount);
+			Balances::<T>::insert((asset_id, to.clone()), new_balance);
+			Ok(())
+		}
+
+		fn burn(asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> DispatchResult {
+			let current_balance = Self::balances((asset_id, from.clone()));
+			let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
+			Balances::<T>::insert((asset_id, from.clone()), new_balance);
+			Ok(())
+		}
+
+		fn balance(asset_id: Self::AssetId, who: &Self::AccountId) -> Self::Balance {
+			Self::balances((asset_id, who.clone()))
+		}
 
-impl<T: Config> AssetManager for Module<T> {
-    type AssetId = T::AssetId;
-    type AccountId = T::AccountId;
-    type Balance = T::Balance;
-
-    fn register_asset(asset_id: Self::AssetId, metadata: AssetMetadata) -> dispatch::DispatchResult {
-        <Assets<T>>::insert(asset_id, metadata);
-        Ok(())
-    }
-
-    fn get_asset(asset_id: Self::AssetId) -> Option<AssetMetadata> {
-        <Assets<T>>::get(asset_id)
-    }
-
-    fn mint(asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult {
-        let new_balance = Self::balances((asset_id, to.clone())).saturating_add(amount);
-        <Balances<T>>::insert((asset_id, to.clone()), new_balance);
-        Ok(())
-    }
-
-    fn burn(asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult {
-        let current_balance = Self::balances((asset_id, from.clone()));
-        let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
-        <Balances<T>>::insert((asset_id, from.clone()), new_balance);
-        Ok(())
-    }
+		fn transfer(
+			asset_id: Self::AssetId,
+			from: Self::AccountId,
+			to: Self::AccountId,
+			amount: Self::Balance,
+		) -> DispatchResult {
+			if from == to {
+				ensure!(Self::balances((asset_id, from)) >= amount, Error::<T>::InsufficientBalance);
+				return Ok(());
+			}
+			let from_balance =
+				Self::balances((asset_id, from.clone())).checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
+			let to_balance =
+				Self::balances((asset_id, to.clone())).checked_add(&amount).ok_or(Error::<T>::BalanceOverflow)?;
+			Balances::<T>::insert((asset_id, from), from_balance);
+			Balances::<T>::insert((asset_id, to), to_balance);
+			Ok(())
+		}
+	}
 }
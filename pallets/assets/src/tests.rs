@@ -1,56 +1,63 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate as pallet_assets;
-    use frame_support::{assert_ok, impl_outer_origin, parameter_types};
-    use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
-    use sp_core::H256;
+use crate::{mock::*, AssetMetadata, Error, Event, GenesisConfig};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::BuildStorage;
 
-    impl_outer_origin! {
-        pub enum Origin for Test {}
-    }
+fn test_asset() -> AssetMetadata {
+	AssetMetadata { name: b"Test Asset".to_vec(), symbol: b"TST".to_vec(), decimals: 10 }
+}
 
-    #[derive(Clone, Eq, PartialEq)]
-    pub struct Test;
-    parameter_types! {
-        pub const BlockHashCount: u64 = 250;
-    }
-    impl frame_system::Config for Test {
-        type BaseCallFilter = frame_support::traits::Everything;
-        type BlockWeights = ();
-        type BlockLength = ();
-        type DbWeight = ();
-        type Origin = Origin;
-        type Call = ();
-        type Index = u64;
-        type BlockNumber = u64;
-        type Hash = H256;
-        type Hashing = BlakeTwo256;
-        type AccountId = u64;
-        type Lookup = IdentityLookup<Self::AccountId>;
-        type Header = Header;
-        type Event = ();
-        type BlockHashCount = BlockHashCount;
-        type Version = ();
-        type PalletInfo = PalletInfo;
-        type AccountData = ();
-        type OnNewAccount = ();
-        type OnKilledAccount = ();
-        type SystemWeightInfo = ();
-    }
+#[test]
+fn it_registers_an_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AssetModule::register_asset(RuntimeOrigin::signed(1), 1, test_asset()));
+		assert_eq!(AssetModule::assets(1), Some(test_asset()));
+		System::assert_last_event(Event::AssetRegistered(1).into());
+	});
+}
 
-    impl Config for Test {
-        type Event = ();
-    }
+#[test]
+fn it_transfers_between_accounts() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AssetModule::mint(RuntimeOrigin::signed(1), 1, 1, 100));
+		assert_ok!(AssetModule::transfer(RuntimeOrigin::signed(1), 1, 2, 40));
+		assert_eq!(AssetModule::balances((1, 1)), 60);
+		assert_eq!(AssetModule::balances((1, 2)), 40);
+		System::assert_last_event(Event::AssetTransferred(1, 1, 2, 40).into());
+		assert_noop!(
+			AssetModule::transfer(RuntimeOrigin::signed(2), 1, 1, 41),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
 
-    type AssetModule = Module<Test>;
+#[test]
+fn it_burns_no_more_than_the_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetModule::mint(RuntimeOrigin::signed(1), 1, 1, 100));
+		assert_ok!(AssetModule::burn(RuntimeOrigin::signed(1), 1, 1, 30));
+		assert_eq!(AssetModule::balances((1, 1)), 70);
+		assert_noop!(
+			AssetModule::burn(RuntimeOrigin::signed(1), 1, 1, 71),
+			Error::<Test>::InsufficientBalance
+		);
+	});
+}
 
-    #[test]
-    fn it_registers_an_asset() {
-        new_test_ext().execute_with(|| {
-            // Test registration of asset
-            assert_ok!(AssetModule::register_asset(Origin::signed(1), 1, AssetMetadata { name: b"Test Asset".to_vec() }));
-            assert_eq!(AssetModule::get_asset(1), Some(AssetMetadata { name: b"Test Asset".to_vec() }));
-        });
-    }
+#[test]
+fn it_registers_genesis_assets_and_balances() {
+	let storage = RuntimeGenesisConfig {
+		system: Default::default(),
+		asset_module: GenesisConfig::<Test> {
+			assets: vec![(1, b"Gem".to_vec(), b"GEM".to_vec(), 12)],
+			balances: vec![(1, 5, 100)],
+		},
+	}
+	.build_storage()
+	.unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		assert_eq!(AssetModule::assets(1).map(|asset| asset.decimals), Some(12));
+		assert_eq!(AssetModule::balances((1, 5)), 100);
+	});
 }
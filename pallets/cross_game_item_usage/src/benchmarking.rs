@@ -1,26 +1,406 @@
+//! Benchmarking setup for pallet-cross-game-item-usage
 #![cfg(feature = "runtime-benchmarks")]
-
 use super::*;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_system::RawOrigin;
-
-benchmarks! {
-    register_game {
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), 1, GameMetadata { name: b"Test Game".to_vec() })
-    verify {
-        assert_eq!(Games::<T>::get(1), Some(GameMetadata { name: b"Test Game".to_vec() }));
-    }
-
-    transfer_item {
-        let caller: T::AccountId = whitelisted_caller();
-        Games::<T>::insert(1, GameMetadata { name: b"Test Game".to_vec() });
-        Games::<T>::insert(2, GameMetadata { name: b"Test Game 2".to_vec() });
-        GameItems::<T>::insert((1, 1), caller.clone());
-    }: _(RawOrigin::Signed(caller), 1, 1, 2)
-    verify {
-        assert_eq!(GameItems::<T>::get((2, 1)), caller);
-    }
+
+#[allow(unused)]
+use crate::Pallet as CrossGameItemUsage;
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, Hooks};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_assets::{AssetManager, AssetMetadata};
+use sp_runtime::traits::Bounded;
+use sp_std::{collections::btree_map::BTreeMap, vec};
+
+fn metadata<T: Config>() -> GameMetadataOf<T> {
+	let name = vec![b'g'; T::MaxGameNameLength::get() as usize];
+	GameMetadata { name: name.try_into().expect("name fits the configured bound") }
+}
+
+fn key<T: Config>(i: u32) -> AttributeKeyOf<T> {
+	let mut key = vec![b'k'; T::MaxAttributeKeyLength::get() as usize];
+	key[0] = i as u8;
+	key.try_into().expect("key fits the configured bound")
+}
+
+/// The largest attribute set an item may carry.
+fn attributes<T: Config>() -> AttributesOf<T> {
+	let value = vec![b'v'; T::MaxAttributeStringLength::get() as usize];
+	let attributes = (0..T::MaxAttributes::get())
+		.map(|i| (key::<T>(i), AttributeValue::String(value.clone().try_into().expect("value fits the configured bound"))))
+		.collect::<BTreeMap<_, _>>();
+	attributes.try_into().expect("attributes fit the configured bound")
+}
+
+/// A schema with a field for every attribute [`attributes`] produces.
+fn schema<T: Config>() -> AttributeSchemaOf<T> {
+	let schema = (0..T::MaxAttributes::get()).map(|i| (key::<T>(i), AttributeKind::String)).collect::<BTreeMap<_, _>>();
+	schema.try_into().expect("schema fits the configured bound")
 }
 
-impl_benchmark_test_suite!(CrossGameItemUsageModule, crate::mock::new_test_ext(), crate::mock::Test);
+/// A table renaming every attribute to itself, so the translated item still fits [`schema`].
+fn translation_table<T: Config>() -> TranslationTableOf<T> {
+	let table = (0..T::MaxAttributes::get())
+		.map(|i| (key::<T>(i), AttributeTranslation::Map { to: key::<T>(i), numerator: 3, denominator: 2 }))
+		.collect::<BTreeMap<_, _>>();
+	table.try_into().expect("table fits the configured bound")
+}
+
+#[benchmarks(where T::GameItemId: From<u32>, T::AssetId: From<u32>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register_game() {
+		let caller: T::AccountId = whitelisted_caller();
+		#[extrinsic_call]
+		register_game(RawOrigin::Signed(caller), 1, metadata::<T>());
+
+		assert_eq!(Games::<T>::get(1), Some(metadata::<T>()));
+	}
+
+	#[benchmark]
+	fn transfer_item() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id: T::GameItemId = 1u32.into();
+		GameItems::<T>::insert((1, item_id), caller.clone());
+		ItemAttributes::<T>::insert((1, item_id), attributes::<T>());
+		Translations::<T>::insert(2, 1, translation_table::<T>());
+		AttributeSchemas::<T>::insert(2, schema::<T>());
+		Games::<T>::insert(2, metadata::<T>());
+		Exports::<T>::insert((1, 2, 0), ());
+		Imports::<T>::insert((2, 1, 0), ());
+		let treasury: T::AccountId = account("treasury", 0, 0);
+		T::Assets::register_asset(1u32.into(), AssetMetadata::default()).expect("asset registers");
+		T::Assets::mint(1u32.into(), caller.clone(), 100u32.into()).expect("asset mints");
+		ImportFees::<T>::insert(2, ImportFee { asset_id: 1u32.into(), amount: 100u32.into(), treasury });
+		ImportQuotas::<T>::insert(2, 1, ImportQuota { max_items: 1, period: 10u32.into() });
+		#[extrinsic_call]
+		transfer_item(RawOrigin::Signed(caller.clone()), item_id, 1, 2);
+
+		assert_eq!(GameItems::<T>::get((2, item_id)), Some(caller));
+	}
+
+	#[benchmark]
+	fn list_for_rent() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id: T::GameItemId = 1u32.into();
+		GameItems::<T>::insert((1, item_id), caller.clone());
+		#[extrinsic_call]
+		list_for_rent(RawOrigin::Signed(caller), 1, item_id, 10u32.into(), 10u32.into());
+
+		assert!(RentalListings::<T>::contains_key((1, item_id)));
+	}
+
+	#[benchmark]
+	fn cancel_rental_listing() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id: T::GameItemId = 1u32.into();
+		GameItems::<T>::insert((1, item_id), caller.clone());
+		RentalListings::<T>::insert((1, item_id), RentalListing { price_per_block: 10u32.into(), max_duration: 10u32.into() });
+		#[extrinsic_call]
+		cancel_rental_listing(RawOrigin::Signed(caller), 1, item_id);
+
+		assert!(!RentalListings::<T>::contains_key((1, item_id)));
+	}
+
+	#[benchmark]
+	fn rent() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let renter: T::AccountId = whitelisted_caller();
+		let item_id: T::GameItemId = 1u32.into();
+		T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value() / 2u32.into());
+		T::Currency::make_free_balance_be(&renter, BalanceOf::<T>::max_value() / 2u32.into());
+		GameItems::<T>::insert((1, item_id), owner);
+		RentalListings::<T>::insert((1, item_id), RentalListing { price_per_block: 10u32.into(), max_duration: 10u32.into() });
+		#[extrinsic_call]
+		rent(RawOrigin::Signed(renter.clone()), 1, item_id, 5u32.into());
+
+		assert_eq!(Pallet::<T>::current_user(1, item_id), Some(renter));
+	}
+
+	#[benchmark]
+	fn mint_item() {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let item_id: T::GameItemId = 1u32.into();
+		let owner_of_game: T::AccountId = account("game", 0, 0);
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, owner_of_game);
+		GameOperators::<T>::insert(1, &caller, ());
+		AttributeSchemas::<T>::insert(1, schema::<T>());
+		#[extrinsic_call]
+		mint_item(RawOrigin::Signed(caller), 1, item_id, 0, owner.clone(), attributes::<T>());
+
+		assert_eq!(GameItems::<T>::get((1, item_id)), Some(owner));
+	}
+
+	#[benchmark]
+	fn burn_item() {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let item_id: T::GameItemId = 1u32.into();
+		let owner_of_game: T::AccountId = account("game", 0, 0);
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, owner_of_game);
+		GameOperators::<T>::insert(1, &caller, ());
+		GameItems::<T>::insert((1, item_id), owner.clone());
+		ItemsByOwner::<T>::insert(&owner, (1, item_id), ());
+		#[extrinsic_call]
+		burn_item(RawOrigin::Signed(caller), 1, item_id);
+
+		assert!(!GameItems::<T>::contains_key((1, item_id)));
+	}
+
+	#[benchmark]
+	fn update_game_metadata() {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("game", 0, 0);
+		Games::<T>::insert(1, GameMetadata { name: Default::default() });
+		GameOwners::<T>::insert(1, owner);
+		GameOperators::<T>::insert(1, &caller, ());
+		#[extrinsic_call]
+		update_game_metadata(RawOrigin::Signed(caller), 1, metadata::<T>());
+
+		assert_eq!(Games::<T>::get(1), Some(metadata::<T>()));
+	}
+
+	#[benchmark]
+	fn add_operator() {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		#[extrinsic_call]
+		add_operator(RawOrigin::Signed(caller), 1, operator.clone());
+
+		assert!(GameOperators::<T>::contains_key(1, operator));
+	}
+
+	#[benchmark]
+	fn remove_operator() {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, 0);
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		GameOperators::<T>::insert(1, &operator, ());
+		#[extrinsic_call]
+		remove_operator(RawOrigin::Signed(caller), 1, operator.clone());
+
+		assert!(!GameOperators::<T>::contains_key(1, operator));
+	}
+
+	#[benchmark]
+	fn allow_export() {
+		let caller: T::AccountId = whitelisted_caller();
+		Games::<T>::insert(1, metadata::<T>());
+		Games::<T>::insert(2, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		#[extrinsic_call]
+		allow_export(RawOrigin::Signed(caller), 1, 2, 0);
+
+		assert!(Exports::<T>::contains_key((1, 2, 0)));
+	}
+
+	#[benchmark]
+	fn revoke_export() {
+		let caller: T::AccountId = whitelisted_caller();
+		GameOwners::<T>::insert(1, caller.clone());
+		Exports::<T>::insert((1, 2, 0), ());
+		#[extrinsic_call]
+		revoke_export(RawOrigin::Signed(caller), 1, 2, 0);
+
+		assert!(!Exports::<T>::contains_key((1, 2, 0)));
+	}
+
+	#[benchmark]
+	fn allow_import() {
+		let caller: T::AccountId = whitelisted_caller();
+		Games::<T>::insert(1, metadata::<T>());
+		Games::<T>::insert(2, metadata::<T>());
+		GameOwners::<T>::insert(2, caller.clone());
+		#[extrinsic_call]
+		allow_import(RawOrigin::Signed(caller), 2, 1, 0);
+
+		assert!(Imports::<T>::contains_key((2, 1, 0)));
+	}
+
+	#[benchmark]
+	fn revoke_import() {
+		let caller: T::AccountId = whitelisted_caller();
+		GameOwners::<T>::insert(2, caller.clone());
+		Imports::<T>::insert((2, 1, 0), ());
+		#[extrinsic_call]
+		revoke_import(RawOrigin::Signed(caller), 2, 1, 0);
+
+		assert!(!Imports::<T>::contains_key((2, 1, 0)));
+	}
+
+	#[benchmark]
+	fn set_translation() {
+		let caller: T::AccountId = whitelisted_caller();
+		Games::<T>::insert(1, metadata::<T>());
+		Games::<T>::insert(2, metadata::<T>());
+		GameOwners::<T>::insert(2, caller.clone());
+		#[extrinsic_call]
+		set_translation(RawOrigin::Signed(caller), 2, 1, translation_table::<T>());
+
+		assert!(Translations::<T>::contains_key(2, 1));
+	}
+
+	#[benchmark]
+	fn clear_translation() {
+		let caller: T::AccountId = whitelisted_caller();
+		GameOwners::<T>::insert(2, caller.clone());
+		Translations::<T>::insert(2, 1, translation_table::<T>());
+		#[extrinsic_call]
+		clear_translation(RawOrigin::Signed(caller), 2, 1);
+
+		assert!(!Translations::<T>::contains_key(2, 1));
+	}
+
+	#[benchmark]
+	fn set_attribute_schema() {
+		let caller: T::AccountId = whitelisted_caller();
+		GameOwners::<T>::insert(1, caller.clone());
+		#[extrinsic_call]
+		set_attribute_schema(RawOrigin::Signed(caller), 1, schema::<T>());
+
+		assert_eq!(AttributeSchemas::<T>::get(1), Some(schema::<T>()));
+	}
+
+	#[benchmark]
+	fn update_attributes() {
+		let caller: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let item_id: T::GameItemId = 1u32.into();
+		GameOwners::<T>::insert(1, caller.clone());
+		AttributeSchemas::<T>::insert(1, schema::<T>());
+		GameItems::<T>::insert((1, item_id), owner);
+		#[extrinsic_call]
+		update_attributes(RawOrigin::Signed(caller), 1, item_id, attributes::<T>());
+
+		assert_eq!(ItemAttributes::<T>::get((1, item_id)), attributes::<T>());
+	}
+
+	#[benchmark]
+	fn transfer_ownership() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let to: T::AccountId = account("to", 0, 0);
+		let item_id: T::GameItemId = 1u32.into();
+		GameItems::<T>::insert((1, item_id), owner.clone());
+		ItemsByOwner::<T>::insert(&owner, (1, item_id), ());
+		ApprovedForAll::<T>::insert(&owner, &caller, ());
+		#[extrinsic_call]
+		transfer_ownership(RawOrigin::Signed(caller), 1, item_id, to.clone());
+
+		assert_eq!(GameItems::<T>::get((1, item_id)), Some(to));
+	}
+
+	#[benchmark]
+	fn approve() {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let item_id: T::GameItemId = 1u32.into();
+		GameItems::<T>::insert((1, item_id), caller.clone());
+		#[extrinsic_call]
+		approve(RawOrigin::Signed(caller), 1, item_id, delegate.clone());
+
+		assert_eq!(Approvals::<T>::get((1, item_id)), Some(delegate));
+	}
+
+	#[benchmark]
+	fn cancel_approval() {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let item_id: T::GameItemId = 1u32.into();
+		GameItems::<T>::insert((1, item_id), caller.clone());
+		Approvals::<T>::insert((1, item_id), delegate);
+		#[extrinsic_call]
+		cancel_approval(RawOrigin::Signed(caller), 1, item_id);
+
+		assert!(!Approvals::<T>::contains_key((1, item_id)));
+	}
+
+	#[benchmark]
+	fn approve_all() {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		#[extrinsic_call]
+		approve_all(RawOrigin::Signed(caller.clone()), delegate.clone(), true);
+
+		assert!(ApprovedForAll::<T>::contains_key(caller, delegate));
+	}
+
+	#[benchmark]
+	fn set_import_fee() {
+		let caller: T::AccountId = whitelisted_caller();
+		let treasury: T::AccountId = account("treasury", 0, 0);
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		T::Assets::register_asset(1u32.into(), AssetMetadata::default()).expect("asset registers");
+		#[extrinsic_call]
+		set_import_fee(RawOrigin::Signed(caller), 1, 1u32.into(), 100u32.into(), treasury);
+
+		assert!(ImportFees::<T>::contains_key(1));
+	}
+
+	#[benchmark]
+	fn clear_import_fee() {
+		let caller: T::AccountId = whitelisted_caller();
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		ImportFees::<T>::insert(1, ImportFee { asset_id: 1u32.into(), amount: 100u32.into(), treasury: caller.clone() });
+		#[extrinsic_call]
+		clear_import_fee(RawOrigin::Signed(caller), 1);
+
+		assert!(!ImportFees::<T>::contains_key(1));
+	}
+
+	#[benchmark]
+	fn set_import_quota() {
+		let caller: T::AccountId = whitelisted_caller();
+		Games::<T>::insert(1, metadata::<T>());
+		Games::<T>::insert(2, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		#[extrinsic_call]
+		set_import_quota(RawOrigin::Signed(caller), 1, 2, 10, 100u32.into());
+
+		assert!(ImportQuotas::<T>::contains_key(1, 2));
+	}
+
+	#[benchmark]
+	fn clear_import_quota() {
+		let caller: T::AccountId = whitelisted_caller();
+		Games::<T>::insert(1, metadata::<T>());
+		GameOwners::<T>::insert(1, caller.clone());
+		ImportQuotas::<T>::insert(1, 2, ImportQuota { max_items: 10, period: 100u32.into() });
+		ImportUsages::<T>::insert(1, 2, ImportUsage { period_start: 0u32.into(), count: 1 });
+		#[extrinsic_call]
+		clear_import_quota(RawOrigin::Signed(caller), 1, 2);
+
+		assert!(!ImportQuotas::<T>::contains_key(1, 2));
+	}
+
+	#[benchmark]
+	fn expire_rentals(n: Linear<0, { T::MaxRentalsPerBlock::get() }>) {
+		let renter: T::AccountId = whitelisted_caller();
+		let now: BlockNumberFor<T> = 10u32.into();
+		let mut expiries = BoundedVec::default();
+		for i in 0..n {
+			let item_id: T::GameItemId = i.into();
+			Rentals::<T>::insert((1, item_id), Rental { renter: renter.clone(), expires_at: now });
+			expiries.try_push((1, item_id)).expect("n is within MaxRentalsPerBlock");
+		}
+		RentalExpiries::<T>::insert(now, expiries);
+
+		#[block]
+		{
+			CrossGameItemUsage::<T>::on_initialize(now);
+		}
+
+		assert_eq!(Rentals::<T>::iter().count(), 0);
+	}
+
+	impl_benchmark_test_suite!(CrossGameItemUsage, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,80 +1,1102 @@
+//! # Cross-Game Item Usage Pallet
+//!
+//! Lets games register on chain and issue items to players, and lets players carry their items
+//! from one registered game to another where both games have agreed to the move. Whoever
+//! registers a game owns it and may appoint operator accounts; only the owner and operators may
+//! mint and burn the game's items, update their attributes or change the game's metadata.
+//!
+//! Items carry typed attributes checked against the game's schema. When an item moves to another
+//! game, its attributes are rewritten with the destination game's translation table. Destination
+//! games may charge an import fee in a pallet-assets token and cap how many items they let in
+//! from each source game per period.
+//!
+//! Players may give items to one another, directly or through an account they approved such as a
+//! marketplace. Item owners may also rent an item out for a fixed number of blocks: the renter
+//! gains the right to use it while ownership stays with the owner, and usage returns to the owner
+//! when the rental term ends.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
-use frame_system::ensure_signed;
-use sp_std::vec::Vec;
+pub use pallet::*;
 
-pub trait CrossGameItemUsage {
-    type GameItemId;
-    type AccountId;
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult, traits::Get, BoundedBTreeMap, BoundedVec, CloneNoBound, EqNoBound,
+	PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Kind of item within a game, such as "sword" or "mount". Games agree on which classes may move
+/// between them.
+pub type ItemClassId = u32;
+
+/// Descriptive information a game publishes when it registers.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(NameLimit))]
+#[codec(mel_bound())]
+pub struct GameMetadata<NameLimit: Get<u32>> {
+	/// Human readable name of the game.
+	pub name: BoundedVec<u8, NameLimit>,
+}
+
+/// Type of an attribute field in a game's schema.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AttributeKind {
+	U32,
+	Bool,
+	/// A string of at most `MaxAttributeStringLength` bytes.
+	String,
+	/// One of `variants` choices, numbered from zero.
+	Enum { variants: u8 },
+}
+
+/// Value of an item attribute.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(StringLimit))]
+#[codec(mel_bound())]
+pub enum AttributeValue<StringLimit: Get<u32>> {
+	U32(u32),
+	Bool(bool),
+	String(BoundedVec<u8, StringLimit>),
+	Enum(u8),
+}
 
-    fn register_game(game_id: u32, game_metadata: GameMetadata) -> dispatch::DispatchResult;
-    fn get_game(game_id: u32) -> Option<GameMetadata>;
-    fn transfer_item(item_id: Self::GameItemId, from_game: u32, to_game: u32, owner: Self::AccountId) -> dispatch::DispatchResult;
+impl<StringLimit: Get<u32>> AttributeValue<StringLimit> {
+	/// Whether this value may be stored in a field of `kind`.
+	pub fn is_of_kind(&self, kind: &AttributeKind) -> bool {
+		match (self, kind) {
+			(AttributeValue::U32(_), AttributeKind::U32) |
+			(AttributeValue::Bool(_), AttributeKind::Bool) |
+			(AttributeValue::String(_), AttributeKind::String) => true,
+			(AttributeValue::Enum(variant), AttributeKind::Enum { variants }) => variant < variants,
+			_ => false,
+		}
+	}
 }
 
-decl_storage! {
-    trait Store for Module<T: Config> as CrossGameItemUsageModule {
-        pub Games get(fn games): map hasher(blake2_128_concat) u32 => Option<GameMetadata>;
-        pub GameItems get(fn game_items): map hasher(blake2_128_concat) (u32, T::GameItemId) => T::AccountId;
-    }
+/// How one attribute is rewritten when an item arrives from another game.
+#[derive(
+	Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(KeyLimit))]
+#[codec(mel_bound())]
+pub enum AttributeTranslation<KeyLimit: Get<u32>> {
+	/// Store the value under `to`. Numeric values are multiplied by `numerator / denominator`.
+	Map { to: BoundedVec<u8, KeyLimit>, numerator: u32, denominator: u32 },
+	/// Leave the attribute behind.
+	Drop,
 }
 
-decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
-        fn register_game(origin, game_id: u32, game_metadata: GameMetadata) -> dispatch::DispatchResult {
-            let _sender = ensure_signed(origin)?;
-            <Games>::insert(game_id, game_metadata);
-            Self::deposit_event(RawEvent::GameRegistered(game_id));
-            Ok(())
-        }
-
-        fn transfer_item(origin, item_id: T::GameItemId, from_game: u32, to_game: u32) -> dispatch::DispatchResult {
-            let owner = ensure_signed(origin)?;
-            ensure!(<GameItems<T>>::contains_key((from_game, item_id)), Error::<T>::ItemNotFound);
-            let item_owner = Self::game_items((from_game, item_id));
-            ensure!(item_owner == owner, Error::<T>::NotItemOwner);
-            <GameItems<T>>::remove((from_game, item_id));
-            <GameItems<T>>::insert((to_game, item_id), owner.clone());
-            Self::deposit_event(RawEvent::ItemTransferred(item_id, from_game, to_game, owner));
-            Ok(())
-        }
-    }
+/// Terms under which an owner offers an item for rent.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RentalListing<Balance, BlockNumber> {
+	pub price_per_block: Balance,
+	pub max_duration: BlockNumber,
 }
 
-decl_event!(
-    pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId, GameItemId = <T as Config>::GameItemId {
-        GameRegistered(u32),
-        ItemTransferred(GameItemId, u32, u32, AccountId),
-    }
-);
-
-decl_error! {
-    pub enum Error for Module<T: Config> {
-        ItemNotFound,
-        NotItemOwner,
-    }
+/// What a game charges to let an item in from another game, paid to the game's treasury.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ImportFee<AccountId, AssetId, Balance> {
+	pub asset_id: AssetId,
+	pub amount: Balance,
+	pub treasury: AccountId,
 }
 
-impl<T: Config> CrossGameItemUsage for Module<T> {
-    type GameItemId = T::GameItemId;
-    type AccountId = T::AccountId;
-
-    fn register_game(game_id: u32, game_metadata: GameMetadata) -> dispatch::DispatchResult {
-        <Games>::insert(game_id, game_metadata);
-        Ok(())
-    }
-
-    fn get_game(game_id: u32) -> Option<GameMetadata> {
-        <Games>::get(game_id)
-    }
-
-    fn transfer_item(item_id: Self::GameItemId, from_game: u32, to_game: u32, owner: Self::AccountId) -> dispatch::DispatchResult {
-        ensure!(<GameItems<T>>::contains_key((from_game, item_id)), Error::<T>::ItemNotFound);
-        let item_owner = Self::game_items((from_game, item_id));
-        ensure!(item_owner == owner, Error::<T>::NotItemOwner);
-        <GameItems<T>>::remove((from_game, item_id));
-        <GameItems<T>>::insert((to_game, item_id), owner.clone());
-        Ok(())
-    }
+/// How many items a game lets in from another game per period of blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ImportQuota<BlockNumber> {
+	pub max_items: u32,
+	pub period: BlockNumber,
+}
+
+/// Items a game has let in from another game during the quota period starting at `period_start`.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ImportUsage<BlockNumber> {
+	pub period_start: BlockNumber,
+	pub count: u32,
+}
+
+/// A time-limited usage right held by a renter. Ownership stays with the item owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Rental<AccountId, BlockNumber> {
+	pub renter: AccountId,
+	pub expires_at: BlockNumber,
+}
+
+pub trait CrossGameItemUsage {
+	type GameItemId;
+	type AccountId;
+	type GameMetadata;
+
+	fn register_game(game_id: u32, owner: Self::AccountId, game_metadata: Self::GameMetadata) -> DispatchResult;
+	fn get_game(game_id: u32) -> Option<Self::GameMetadata>;
+	/// The account that registered a game, or `None` if the game does not exist.
+	fn game_owner(game_id: u32) -> Option<Self::AccountId>;
+	fn transfer_item(item_id: Self::GameItemId, from_game: u32, to_game: u32, owner: Self::AccountId) -> DispatchResult;
+	/// The owner of an item, or `None` if the item does not exist.
+	fn owner_of(game_id: u32, item_id: Self::GameItemId) -> Option<Self::AccountId>;
+	/// Hand an item `from` its owner `to` another account within the same game.
+	fn transfer_ownership(game_id: u32, item_id: Self::GameItemId, from: &Self::AccountId, to: &Self::AccountId) -> DispatchResult;
+	/// The account currently allowed to use an item: the renter during a rental, otherwise the owner.
+	fn item_user(game_id: u32, item_id: Self::GameItemId) -> Option<Self::AccountId>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement},
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_assets::AssetManager;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, SaturatedConversion, Saturating, Zero};
+	use sp_std::{
+		collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+		vec::Vec,
+	};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type GameMetadataOf<T> = GameMetadata<<T as Config>::MaxGameNameLength>;
+	pub type AttributeKeyOf<T> = BoundedVec<u8, <T as Config>::MaxAttributeKeyLength>;
+	pub type AttributeValueOf<T> = AttributeValue<<T as Config>::MaxAttributeStringLength>;
+	pub type AttributesOf<T> = BoundedBTreeMap<AttributeKeyOf<T>, AttributeValueOf<T>, <T as Config>::MaxAttributes>;
+	pub type AttributeSchemaOf<T> = BoundedBTreeMap<AttributeKeyOf<T>, AttributeKind, <T as Config>::MaxAttributes>;
+	pub type AttributeTranslationOf<T> = AttributeTranslation<<T as Config>::MaxAttributeKeyLength>;
+	pub type TranslationTableOf<T> =
+		BoundedBTreeMap<AttributeKeyOf<T>, AttributeTranslationOf<T>, <T as Config>::MaxAttributes>;
+	pub type ImportFeeOf<T> =
+		ImportFee<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, <T as Config>::AssetBalance>;
+	pub type ImportQuotaOf<T> = ImportQuota<BlockNumberFor<T>>;
+	pub type ImportUsageOf<T> = ImportUsage<BlockNumberFor<T>>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching runtime event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Identifier of an item within a game.
+		type GameItemId: Parameter + Member + MaxEncodedLen + Copy;
+		/// Currency used to pay rent to item owners.
+		type Currency: Currency<Self::AccountId>;
+		/// Identifier of a token games charge import fees in.
+		type AssetId: Parameter + Member + MaxEncodedLen + Copy;
+		/// Token amounts of import fees.
+		type AssetBalance: Parameter + Member + MaxEncodedLen + AtLeast32BitUnsigned + Copy;
+		/// The tokens import fees are paid in.
+		type Assets: AssetManager<AssetId = Self::AssetId, AccountId = Self::AccountId, Balance = Self::AssetBalance>;
+		/// Maximum number of rentals that may end in the same block, bounding `on_initialize`.
+		#[pallet::constant]
+		type MaxRentalsPerBlock: Get<u32>;
+		/// Maximum length in bytes of a game's name.
+		#[pallet::constant]
+		type MaxGameNameLength: Get<u32>;
+		/// Maximum length in bytes of an attribute key.
+		#[pallet::constant]
+		type MaxAttributeKeyLength: Get<u32>;
+		/// Maximum length in bytes of a string attribute value.
+		#[pallet::constant]
+		type MaxAttributeStringLength: Get<u32>;
+		/// Maximum number of attributes an item carries, and of fields in a schema or rules in a
+		/// translation table.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Registered games by id.
+	#[pallet::storage]
+	#[pallet::getter(fn games)]
+	pub type Games<T: Config> = StorageMap<_, Blake2_128Concat, u32, GameMetadataOf<T>>;
+
+	/// Account that registered each game.
+	#[pallet::storage]
+	#[pallet::getter(fn game_owners)]
+	pub type GameOwners<T: Config> = StorageMap<_, Blake2_128Concat, u32, T::AccountId>;
+
+	/// Accounts the owner has allowed to act on behalf of each game.
+	#[pallet::storage]
+	pub type GameOperators<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Owner of each item, keyed by the game it currently lives in.
+	#[pallet::storage]
+	#[pallet::getter(fn game_items)]
+	pub type GameItems<T: Config> = StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), T::AccountId>;
+
+	/// Items held by each account, keyed by the game they currently live in.
+	#[pallet::storage]
+	pub type ItemsByOwner<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, (u32, T::GameItemId), ()>;
+
+	/// Class each item was minted with. It stays with the item as it moves between games.
+	#[pallet::storage]
+	#[pallet::getter(fn item_classes)]
+	pub type ItemClasses<T: Config> = StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), ItemClassId, ValueQuery>;
+
+	/// Item classes each game lets leave for another game, keyed by `(from_game, to_game, class)`.
+	#[pallet::storage]
+	pub type Exports<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, ItemClassId>,
+		),
+		(),
+	>;
+
+	/// Item classes each game accepts from another game, keyed by `(to_game, from_game, class)`.
+	#[pallet::storage]
+	pub type Imports<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, u32>,
+			NMapKey<Blake2_128Concat, ItemClassId>,
+		),
+		(),
+	>;
+
+	/// Attributes the issuing game attached to each item.
+	#[pallet::storage]
+	#[pallet::getter(fn item_attributes)]
+	pub type ItemAttributes<T: Config> = StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), AttributesOf<T>, ValueQuery>;
+
+	/// Fields each game's items may carry. Games without a schema accept any attributes.
+	#[pallet::storage]
+	#[pallet::getter(fn attribute_schemas)]
+	pub type AttributeSchemas<T: Config> = StorageMap<_, Blake2_128Concat, u32, AttributeSchemaOf<T>>;
+
+	/// How each game rewrites the attributes of items arriving from another game, keyed by
+	/// `(to_game, from_game)`. Attributes without a rule keep their key and value.
+	#[pallet::storage]
+	#[pallet::getter(fn translations)]
+	pub type Translations<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, u32, TranslationTableOf<T>>;
+
+	/// Fee a game charges for every item it lets in from another game.
+	#[pallet::storage]
+	#[pallet::getter(fn import_fees)]
+	pub type ImportFees<T: Config> = StorageMap<_, Blake2_128Concat, u32, ImportFeeOf<T>>;
+
+	/// How many items a game lets in from another game per period, keyed by
+	/// `(to_game, from_game)`. Imports are unlimited without a quota.
+	#[pallet::storage]
+	#[pallet::getter(fn import_quotas)]
+	pub type ImportQuotas<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, u32, ImportQuotaOf<T>>;
+
+	/// Items let in under each import quota during its current period, keyed by
+	/// `(to_game, from_game)`.
+	#[pallet::storage]
+	#[pallet::getter(fn import_usages)]
+	pub type ImportUsages<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, u32, Blake2_128Concat, u32, ImportUsageOf<T>, ValueQuery>;
+
+	/// Items currently offered for rent.
+	#[pallet::storage]
+	#[pallet::getter(fn rental_listings)]
+	pub type RentalListings<T: Config> =
+		StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), RentalListing<BalanceOf<T>, BlockNumberFor<T>>>;
+
+	/// Items currently rented out.
+	#[pallet::storage]
+	#[pallet::getter(fn rentals)]
+	pub type Rentals<T: Config> =
+		StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), Rental<T::AccountId, BlockNumberFor<T>>>;
+
+	/// Account each owner allowed to hand on a single item.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config> = StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), T::AccountId>;
+
+	/// Accounts, such as marketplaces, each owner allowed to hand on any of their items.
+	#[pallet::storage]
+	pub type ApprovedForAll<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Rentals ending in each block.
+	#[pallet::storage]
+	#[pallet::getter(fn rental_expiries)]
+	pub type RentalExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(u32, T::GameItemId), T::MaxRentalsPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A game was registered.
+		GameRegistered { game_id: u32, owner: T::AccountId },
+		/// A game's metadata was replaced.
+		GameMetadataUpdated { game_id: u32 },
+		/// The owner allowed an account to act on behalf of a game.
+		OperatorAdded { game_id: u32, operator: T::AccountId },
+		/// The owner revoked an operator.
+		OperatorRemoved { game_id: u32, operator: T::AccountId },
+		/// An item moved from one game to another, its attributes rewritten into the destination
+		/// game's schema.
+		ItemTransferred {
+			item_id: T::GameItemId,
+			from_game: u32,
+			to_game: u32,
+			owner: T::AccountId,
+			attributes_before: AttributesOf<T>,
+			attributes_after: AttributesOf<T>,
+		},
+		/// An item was listed for rent.
+		RentalListed {
+			game_id: u32,
+			item_id: T::GameItemId,
+			price_per_block: BalanceOf<T>,
+			max_duration: BlockNumberFor<T>,
+		},
+		/// A rental listing was withdrawn.
+		RentalListingCancelled { game_id: u32, item_id: T::GameItemId },
+		/// An item was rented.
+		ItemRented {
+			game_id: u32,
+			item_id: T::GameItemId,
+			renter: T::AccountId,
+			expires_at: BlockNumberFor<T>,
+			total_price: BalanceOf<T>,
+		},
+		/// A rental term ended and usage returned to the owner.
+		RentalEnded { game_id: u32, item_id: T::GameItemId, renter: T::AccountId },
+		/// A game issued a new item.
+		ItemMinted { game_id: u32, item_id: T::GameItemId, owner: T::AccountId },
+		/// A game destroyed one of its items.
+		ItemBurned { game_id: u32, item_id: T::GameItemId, owner: T::AccountId },
+		/// A game let items of a class leave for another game.
+		ExportAllowed { game_id: u32, to_game: u32, class_id: ItemClassId },
+		/// A game stopped letting items of a class leave for another game.
+		ExportRevoked { game_id: u32, to_game: u32, class_id: ItemClassId },
+		/// A game agreed to accept items of a class from another game.
+		ImportAllowed { game_id: u32, from_game: u32, class_id: ItemClassId },
+		/// A game stopped accepting items of a class from another game.
+		ImportRevoked { game_id: u32, from_game: u32, class_id: ItemClassId },
+		/// A game set how it rewrites attributes of items arriving from another game.
+		TranslationSet { game_id: u32, from_game: u32 },
+		/// A game removed its attribute translation for items from another game.
+		TranslationCleared { game_id: u32, from_game: u32 },
+		/// A game set the fee it charges for items arriving from other games.
+		ImportFeeSet { game_id: u32, asset_id: T::AssetId, amount: T::AssetBalance, treasury: T::AccountId },
+		/// A game stopped charging for items arriving from other games.
+		ImportFeeCleared { game_id: u32 },
+		/// An owner paid a game's import fee to bring an item in.
+		ImportFeePaid { game_id: u32, payer: T::AccountId, asset_id: T::AssetId, amount: T::AssetBalance },
+		/// A game limited how many items it lets in from another game per period.
+		ImportQuotaSet { game_id: u32, from_game: u32, max_items: u32, period: BlockNumberFor<T> },
+		/// A game lifted its import quota for items from another game.
+		ImportQuotaCleared { game_id: u32, from_game: u32 },
+		/// A game published the attribute schema its items follow.
+		AttributeSchemaSet { game_id: u32 },
+		/// A game changed the attributes of one of its items.
+		ItemAttributesUpdated { game_id: u32, item_id: T::GameItemId, attributes: AttributesOf<T> },
+		/// An item changed hands within a game.
+		OwnershipTransferred { game_id: u32, item_id: T::GameItemId, from: T::AccountId, to: T::AccountId },
+		/// An owner allowed `delegate` to hand on one item.
+		Approved { game_id: u32, item_id: T::GameItemId, owner: T::AccountId, delegate: T::AccountId },
+		/// An owner withdrew the approval on one item.
+		ApprovalCancelled { game_id: u32, item_id: T::GameItemId, owner: T::AccountId },
+		/// An owner allowed or stopped allowing `delegate` to hand on any of their items.
+		ApprovalForAll { owner: T::AccountId, delegate: T::AccountId, approved: bool },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No such item exists in the given game.
+		ItemNotFound,
+		/// The caller does not own the item.
+		NotItemOwner,
+		/// The item is currently rented out.
+		ItemRented,
+		/// The item has no rental listing.
+		NotListedForRent,
+		/// Rental duration is zero or exceeds the listing's maximum.
+		InvalidRentalDuration,
+		/// Owners cannot rent their own items.
+		RenterIsOwner,
+		/// Too many rentals already end in the requested block.
+		TooManyRentalsEnding,
+		/// No game is registered under the given id.
+		GameNotFound,
+		/// The caller is neither the game's owner nor one of its operators.
+		NotGameOperator,
+		/// The caller does not own the game.
+		NotGameOwner,
+		/// A game is already registered under this id.
+		GameAlreadyRegistered,
+		/// The account is not an operator of the game.
+		OperatorNotFound,
+		/// The source game does not let this item class leave for the destination game.
+		NotExported,
+		/// The destination game does not accept this item class from the source game.
+		NotAccepted,
+		/// No such export or import is in place.
+		AgreementNotFound,
+		/// A translation rule scales by a zero denominator.
+		InvalidTranslation,
+		/// Two attributes would be translated to the same key.
+		TranslationCollision,
+		/// The game has no attribute translation for items from the other game.
+		TranslationNotFound,
+		/// The attributes name a field missing from the game's schema or hold a value of the
+		/// wrong type.
+		AttributesDoNotMatchSchema,
+		/// The caller neither owns the item nor was approved by its owner.
+		NotApproved,
+		/// The item has no approval to cancel.
+		ApprovalNotFound,
+		/// An item with this id already exists in the game.
+		ItemAlreadyExists,
+		/// The token is not registered with pallet-assets.
+		UnknownAsset,
+		/// The game charges no import fee.
+		ImportFeeNotFound,
+		/// The owner cannot pay the destination game's import fee.
+		CannotPayImportFee,
+		/// An import quota period must last at least one block.
+		InvalidImportQuota,
+		/// The game has no import quota for items from the other game.
+		ImportQuotaNotFound,
+		/// The destination game has let in as many items from the source game as its quota allows
+		/// this period.
+		ImportQuotaExceeded,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let expired = RentalExpiries::<T>::take(now);
+			let count = expired.len() as u32;
+			for (game_id, item_id) in expired {
+				if let Some(rental) = Rentals::<T>::take((game_id, item_id)) {
+					Self::deposit_event(Event::RentalEnded { game_id, item_id, renter: rental.renter });
+				}
+			}
+			T::WeightInfo::expire_rentals(count)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a new game under `game_id`, owned by the caller.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register_game())]
+		pub fn register_game(origin: OriginFor<T>, game_id: u32, game_metadata: GameMetadataOf<T>) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			<Self as CrossGameItemUsage>::register_game(game_id, owner.clone(), game_metadata)?;
+			Self::deposit_event(Event::GameRegistered { game_id, owner });
+			Ok(())
+		}
+
+		/// Move one of the caller's items from `from_game` to `to_game`.
+		///
+		/// Both games must have agreed to the move: `from_game` must export the item's class to
+		/// `to_game`, and `to_game` must accept it from `from_game`. The caller pays `to_game`'s
+		/// import fee, and the move counts toward its import quota for items from `from_game`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::transfer_item())]
+		pub fn transfer_item(origin: OriginFor<T>, item_id: T::GameItemId, from_game: u32, to_game: u32) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let (attributes_before, attributes_after) = Self::do_transfer_item(item_id, from_game, to_game, &owner)?;
+			Self::deposit_event(Event::ItemTransferred {
+				item_id,
+				from_game,
+				to_game,
+				owner,
+				attributes_before,
+				attributes_after,
+			});
+			Ok(())
+		}
+
+		/// Offer an item for rent at `price_per_block` for at most `max_duration` blocks.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::list_for_rent())]
+		pub fn list_for_rent(
+			origin: OriginFor<T>,
+			game_id: u32,
+			item_id: T::GameItemId,
+			price_per_block: BalanceOf<T>,
+			max_duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let item_owner = GameItems::<T>::get((game_id, item_id)).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item_owner == owner, Error::<T>::NotItemOwner);
+			ensure!(!max_duration.is_zero(), Error::<T>::InvalidRentalDuration);
+			RentalListings::<T>::insert((game_id, item_id), RentalListing { price_per_block, max_duration });
+			Self::deposit_event(Event::RentalListed { game_id, item_id, price_per_block, max_duration });
+			Ok(())
+		}
+
+		/// Withdraw a rental offer. An ongoing rental is not affected.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel_rental_listing())]
+		pub fn cancel_rental_listing(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(GameItems::<T>::get((game_id, item_id)) == Some(owner), Error::<T>::NotItemOwner);
+			ensure!(RentalListings::<T>::contains_key((game_id, item_id)), Error::<T>::NotListedForRent);
+			RentalListings::<T>::remove((game_id, item_id));
+			Self::deposit_event(Event::RentalListingCancelled { game_id, item_id });
+			Ok(())
+		}
+
+		/// Rent a listed item for `duration` blocks, paying the owner up front.
+		///
+		/// The renter gains the right to use the item until the rental expires, at which point
+		/// `on_initialize` returns it to the owner. Ownership never changes hands.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::rent())]
+		pub fn rent(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId, duration: BlockNumberFor<T>) -> DispatchResult {
+			let renter = ensure_signed(origin)?;
+			let listing = RentalListings::<T>::get((game_id, item_id)).ok_or(Error::<T>::NotListedForRent)?;
+			ensure!(!duration.is_zero() && duration <= listing.max_duration, Error::<T>::InvalidRentalDuration);
+			ensure!(!Rentals::<T>::contains_key((game_id, item_id)), Error::<T>::ItemRented);
+			let owner = GameItems::<T>::get((game_id, item_id)).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(owner != renter, Error::<T>::RenterIsOwner);
+
+			let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			let mut expiries = RentalExpiries::<T>::get(expires_at);
+			expiries.try_push((game_id, item_id)).map_err(|_| Error::<T>::TooManyRentalsEnding)?;
+
+			let cost = listing.price_per_block.saturating_mul(duration.saturated_into::<u32>().into());
+			T::Currency::transfer(&renter, &owner, cost, ExistenceRequirement::KeepAlive)?;
+
+			RentalExpiries::<T>::insert(expires_at, expiries);
+			Rentals::<T>::insert((game_id, item_id), Rental { renter: renter.clone(), expires_at });
+			Self::deposit_event(Event::ItemRented { game_id, item_id, renter, expires_at, total_price: cost });
+			Ok(())
+		}
+
+		/// Issue a new item of `class_id` in `game_id` to `owner`. Only the game's owner and
+		/// operators may mint.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::mint_item())]
+		pub fn mint_item(
+			origin: OriginFor<T>,
+			game_id: u32,
+			item_id: T::GameItemId,
+			class_id: ItemClassId,
+			owner: T::AccountId,
+			attributes: AttributesOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(!GameItems::<T>::contains_key((game_id, item_id)), Error::<T>::ItemAlreadyExists);
+			Self::ensure_matches_schema(game_id, &attributes)?;
+			GameItems::<T>::insert((game_id, item_id), owner.clone());
+			ItemsByOwner::<T>::insert(&owner, (game_id, item_id), ());
+			ItemClasses::<T>::insert((game_id, item_id), class_id);
+			ItemAttributes::<T>::insert((game_id, item_id), attributes);
+			Self::deposit_event(Event::ItemMinted { game_id, item_id, owner });
+			Ok(())
+		}
+
+		/// Destroy an item of `game_id`. Only the game's owner and operators may burn, and never
+		/// while the item is rented out.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::burn_item())]
+		pub fn burn_item(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			let owner = GameItems::<T>::get((game_id, item_id)).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(!Rentals::<T>::contains_key((game_id, item_id)), Error::<T>::ItemRented);
+			GameItems::<T>::remove((game_id, item_id));
+			ItemsByOwner::<T>::remove(&owner, (game_id, item_id));
+			ItemClasses::<T>::remove((game_id, item_id));
+			ItemAttributes::<T>::remove((game_id, item_id));
+			RentalListings::<T>::remove((game_id, item_id));
+			Approvals::<T>::remove((game_id, item_id));
+			Self::deposit_event(Event::ItemBurned { game_id, item_id, owner });
+			Ok(())
+		}
+
+		/// Replace the metadata of `game_id`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::update_game_metadata())]
+		pub fn update_game_metadata(origin: OriginFor<T>, game_id: u32, game_metadata: GameMetadataOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			Games::<T>::insert(game_id, game_metadata);
+			Self::deposit_event(Event::GameMetadataUpdated { game_id });
+			Ok(())
+		}
+
+		/// Allow `operator` to act on behalf of `game_id`. Only the game's owner may appoint
+		/// operators.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::add_operator())]
+		pub fn add_operator(origin: OriginFor<T>, game_id: u32, operator: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_game_owner(game_id, &sender)?;
+			GameOperators::<T>::insert(game_id, &operator, ());
+			Self::deposit_event(Event::OperatorAdded { game_id, operator });
+			Ok(())
+		}
+
+		/// Revoke an operator of `game_id`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::remove_operator())]
+		pub fn remove_operator(origin: OriginFor<T>, game_id: u32, operator: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_game_owner(game_id, &sender)?;
+			ensure!(GameOperators::<T>::contains_key(game_id, &operator), Error::<T>::OperatorNotFound);
+			GameOperators::<T>::remove(game_id, &operator);
+			Self::deposit_event(Event::OperatorRemoved { game_id, operator });
+			Ok(())
+		}
+
+		/// Let items of `class_id` leave `game_id` for `to_game`.
+		///
+		/// Items only move once `to_game` also accepts the class with [`Pallet::allow_import`].
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::allow_export())]
+		pub fn allow_export(origin: OriginFor<T>, game_id: u32, to_game: u32, class_id: ItemClassId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Games::<T>::contains_key(to_game), Error::<T>::GameNotFound);
+			Exports::<T>::insert((game_id, to_game, class_id), ());
+			Self::deposit_event(Event::ExportAllowed { game_id, to_game, class_id });
+			Ok(())
+		}
+
+		/// Stop items of `class_id` leaving `game_id` for `to_game`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::revoke_export())]
+		pub fn revoke_export(origin: OriginFor<T>, game_id: u32, to_game: u32, class_id: ItemClassId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Exports::<T>::contains_key((game_id, to_game, class_id)), Error::<T>::AgreementNotFound);
+			Exports::<T>::remove((game_id, to_game, class_id));
+			Self::deposit_event(Event::ExportRevoked { game_id, to_game, class_id });
+			Ok(())
+		}
+
+		/// Accept items of `class_id` arriving in `game_id` from `from_game`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::allow_import())]
+		pub fn allow_import(origin: OriginFor<T>, game_id: u32, from_game: u32, class_id: ItemClassId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Games::<T>::contains_key(from_game), Error::<T>::GameNotFound);
+			Imports::<T>::insert((game_id, from_game, class_id), ());
+			Self::deposit_event(Event::ImportAllowed { game_id, from_game, class_id });
+			Ok(())
+		}
+
+		/// Stop accepting items of `class_id` arriving in `game_id` from `from_game`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::revoke_import())]
+		pub fn revoke_import(origin: OriginFor<T>, game_id: u32, from_game: u32, class_id: ItemClassId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Imports::<T>::contains_key((game_id, from_game, class_id)), Error::<T>::AgreementNotFound);
+			Imports::<T>::remove((game_id, from_game, class_id));
+			Self::deposit_event(Event::ImportRevoked { game_id, from_game, class_id });
+			Ok(())
+		}
+
+		/// Set how `game_id` rewrites the attributes of items arriving from `from_game`,
+		/// replacing any table already in place.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_translation())]
+		pub fn set_translation(
+			origin: OriginFor<T>,
+			game_id: u32,
+			from_game: u32,
+			table: TranslationTableOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Games::<T>::contains_key(from_game), Error::<T>::GameNotFound);
+			let valid = table.values().all(|rule| match rule {
+				AttributeTranslation::Map { denominator, .. } => *denominator != 0,
+				AttributeTranslation::Drop => true,
+			});
+			ensure!(valid, Error::<T>::InvalidTranslation);
+			let mut targets = BTreeSet::new();
+			let distinct = table.values().all(|rule| match rule {
+				AttributeTranslation::Map { to, .. } => targets.insert(to),
+				AttributeTranslation::Drop => true,
+			});
+			ensure!(distinct, Error::<T>::TranslationCollision);
+			Translations::<T>::insert(game_id, from_game, table);
+			Self::deposit_event(Event::TranslationSet { game_id, from_game });
+			Ok(())
+		}
+
+		/// Stop rewriting the attributes of items arriving in `game_id` from `from_game`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::clear_translation())]
+		pub fn clear_translation(origin: OriginFor<T>, game_id: u32, from_game: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Translations::<T>::contains_key(game_id, from_game), Error::<T>::TranslationNotFound);
+			Translations::<T>::remove(game_id, from_game);
+			Self::deposit_event(Event::TranslationCleared { game_id, from_game });
+			Ok(())
+		}
+
+		/// Publish the fields items of `game_id` may carry, replacing any schema already in place.
+		///
+		/// Items already stored are not checked again; the schema applies from their next update.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_attribute_schema())]
+		pub fn set_attribute_schema(origin: OriginFor<T>, game_id: u32, schema: AttributeSchemaOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			AttributeSchemas::<T>::insert(game_id, schema);
+			Self::deposit_event(Event::AttributeSchemaSet { game_id });
+			Ok(())
+		}
+
+		/// Replace the attributes of an item of `game_id`. Only the game's owner and operators may
+		/// update attributes.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::update_attributes())]
+		pub fn update_attributes(
+			origin: OriginFor<T>,
+			game_id: u32,
+			item_id: T::GameItemId,
+			attributes: AttributesOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(GameItems::<T>::contains_key((game_id, item_id)), Error::<T>::ItemNotFound);
+			Self::ensure_matches_schema(game_id, &attributes)?;
+			ItemAttributes::<T>::insert((game_id, item_id), attributes.clone());
+			Self::deposit_event(Event::ItemAttributesUpdated { game_id, item_id, attributes });
+			Ok(())
+		}
+
+		/// Give an item of `game_id` to `to`.
+		///
+		/// The caller must own the item, be approved for it, or be approved for all of the owner's
+		/// items. Rented items cannot change hands until the rental ends.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::transfer_ownership())]
+		pub fn transfer_ownership(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId, to: T::AccountId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = GameItems::<T>::get((game_id, item_id)).ok_or(Error::<T>::ItemNotFound)?;
+			let approved = sender == owner ||
+				Approvals::<T>::get((game_id, item_id)).as_ref() == Some(&sender) ||
+				ApprovedForAll::<T>::contains_key(&owner, &sender);
+			ensure!(approved, Error::<T>::NotApproved);
+			<Self as CrossGameItemUsage>::transfer_ownership(game_id, item_id, &owner, &to)?;
+			Self::deposit_event(Event::OwnershipTransferred { game_id, item_id, from: owner, to });
+			Ok(())
+		}
+
+		/// Allow `delegate` to hand on one of the caller's items. The approval lapses when the item
+		/// changes hands.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId, delegate: T::AccountId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(GameItems::<T>::get((game_id, item_id)).as_ref() == Some(&owner), Error::<T>::NotItemOwner);
+			Approvals::<T>::insert((game_id, item_id), &delegate);
+			Self::deposit_event(Event::Approved { game_id, item_id, owner, delegate });
+			Ok(())
+		}
+
+		/// Withdraw the approval on one of the caller's items.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub fn cancel_approval(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(GameItems::<T>::get((game_id, item_id)).as_ref() == Some(&owner), Error::<T>::NotItemOwner);
+			ensure!(Approvals::<T>::contains_key((game_id, item_id)), Error::<T>::ApprovalNotFound);
+			Approvals::<T>::remove((game_id, item_id));
+			Self::deposit_event(Event::ApprovalCancelled { game_id, item_id, owner });
+			Ok(())
+		}
+
+		/// Allow or stop allowing `delegate` to hand on any of the caller's items, in every game.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::approve_all())]
+		pub fn approve_all(origin: OriginFor<T>, delegate: T::AccountId, approved: bool) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			if approved {
+				ApprovedForAll::<T>::insert(&owner, &delegate, ());
+			} else {
+				ApprovedForAll::<T>::remove(&owner, &delegate);
+			}
+			Self::deposit_event(Event::ApprovalForAll { owner, delegate, approved });
+			Ok(())
+		}
+
+		/// Charge `amount` of `asset_id`, paid to `treasury`, for every item arriving in `game_id`
+		/// from another game, replacing any fee already in place.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::set_import_fee())]
+		pub fn set_import_fee(
+			origin: OriginFor<T>,
+			game_id: u32,
+			asset_id: T::AssetId,
+			amount: T::AssetBalance,
+			treasury: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(T::Assets::get_asset(asset_id).is_some(), Error::<T>::UnknownAsset);
+			ImportFees::<T>::insert(game_id, ImportFee { asset_id, amount, treasury: treasury.clone() });
+			Self::deposit_event(Event::ImportFeeSet { game_id, asset_id, amount, treasury });
+			Ok(())
+		}
+
+		/// Stop charging for items arriving in `game_id`.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::clear_import_fee())]
+		pub fn clear_import_fee(origin: OriginFor<T>, game_id: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(ImportFees::<T>::contains_key(game_id), Error::<T>::ImportFeeNotFound);
+			ImportFees::<T>::remove(game_id);
+			Self::deposit_event(Event::ImportFeeCleared { game_id });
+			Ok(())
+		}
+
+		/// Let at most `max_items` items into `game_id` from `from_game` in every period of
+		/// `period` blocks, replacing any quota already in place. Periods start at multiples of
+		/// `period`.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::set_import_quota())]
+		pub fn set_import_quota(
+			origin: OriginFor<T>,
+			game_id: u32,
+			from_game: u32,
+			max_items: u32,
+			period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(Games::<T>::contains_key(from_game), Error::<T>::GameNotFound);
+			ensure!(!period.is_zero(), Error::<T>::InvalidImportQuota);
+			ImportQuotas::<T>::insert(game_id, from_game, ImportQuota { max_items, period });
+			Self::deposit_event(Event::ImportQuotaSet { game_id, from_game, max_items, period });
+			Ok(())
+		}
+
+		/// Lift the limit on items arriving in `game_id` from `from_game`.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::clear_import_quota())]
+		pub fn clear_import_quota(origin: OriginFor<T>, game_id: u32, from_game: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_operator(game_id, &sender)?;
+			ensure!(ImportQuotas::<T>::contains_key(game_id, from_game), Error::<T>::ImportQuotaNotFound);
+			ImportQuotas::<T>::remove(game_id, from_game);
+			ImportUsages::<T>::remove(game_id, from_game);
+			Self::deposit_event(Event::ImportQuotaCleared { game_id, from_game });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Ensure `who` owns or operates `game_id`.
+		fn ensure_operator(game_id: u32, who: &T::AccountId) -> DispatchResult {
+			let owner = GameOwners::<T>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(owner == *who || GameOperators::<T>::contains_key(game_id, who), Error::<T>::NotGameOperator);
+			Ok(())
+		}
+
+		/// Ensure `who` owns `game_id`.
+		fn ensure_game_owner(game_id: u32, who: &T::AccountId) -> DispatchResult {
+			let owner = GameOwners::<T>::get(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(owner == *who, Error::<T>::NotGameOwner);
+			Ok(())
+		}
+
+		/// Ensure every attribute is a field of `game_id`'s schema holding a value of that field's
+		/// type. Games without a schema accept any attributes.
+		fn ensure_matches_schema(game_id: u32, attributes: &AttributesOf<T>) -> DispatchResult {
+			if let Some(schema) = AttributeSchemas::<T>::get(game_id) {
+				let matches = attributes
+					.iter()
+					.all(|(key, value)| schema.get(key).map_or(false, |kind| value.is_of_kind(kind)));
+				ensure!(matches, Error::<T>::AttributesDoNotMatchSchema);
+			}
+			Ok(())
+		}
+
+		/// Items `who` holds across all games.
+		pub fn items_of(who: &T::AccountId) -> Vec<(u32, T::GameItemId)> {
+			ItemsByOwner::<T>::iter_key_prefix(who).collect()
+		}
+
+		/// The account allowed to use an item right now, or `None` if the item does not exist.
+		pub fn current_user(game_id: u32, item_id: T::GameItemId) -> Option<T::AccountId> {
+			let owner = GameItems::<T>::get((game_id, item_id))?;
+			match Rentals::<T>::get((game_id, item_id)) {
+				Some(rental) => Some(rental.renter),
+				None => Some(owner),
+			}
+		}
+
+		/// Move `owner`'s item between games once both have agreed, rewriting its attributes with the
+		/// destination game's translation table. The rewritten attributes must fit the destination
+		/// game's schema, the destination game's import quota must have room, and `owner` pays its
+		/// import fee. Returns the attributes before and after translation.
+		fn do_transfer_item(
+			item_id: T::GameItemId,
+			from_game: u32,
+			to_game: u32,
+			owner: &T::AccountId,
+		) -> Result<(AttributesOf<T>, AttributesOf<T>), DispatchError> {
+			let item_owner = GameItems::<T>::get((from_game, item_id)).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(item_owner == *owner, Error::<T>::NotItemOwner);
+			ensure!(!Rentals::<T>::contains_key((from_game, item_id)), Error::<T>::ItemRented);
+			ensure!(Games::<T>::contains_key(to_game), Error::<T>::GameNotFound);
+			ensure!(!GameItems::<T>::contains_key((to_game, item_id)), Error::<T>::ItemAlreadyExists);
+			let class_id = ItemClasses::<T>::get((from_game, item_id));
+			ensure!(Exports::<T>::contains_key((from_game, to_game, class_id)), Error::<T>::NotExported);
+			ensure!(Imports::<T>::contains_key((to_game, from_game, class_id)), Error::<T>::NotAccepted);
+			let attributes_before = ItemAttributes::<T>::get((from_game, item_id));
+			let attributes_after = match Translations::<T>::get(to_game, from_game) {
+				Some(table) => Self::translate(&attributes_before, &table)?,
+				None => attributes_before.clone(),
+			};
+			Self::ensure_matches_schema(to_game, &attributes_after)?;
+			let usage = Self::count_import(to_game, from_game)?;
+			if let Some(fee) = ImportFees::<T>::get(to_game) {
+				T::Assets::transfer(fee.asset_id, owner.clone(), fee.treasury, fee.amount)
+					.map_err(|_| Error::<T>::CannotPayImportFee)?;
+				Self::deposit_event(Event::ImportFeePaid {
+					game_id: to_game,
+					payer: owner.clone(),
+					asset_id: fee.asset_id,
+					amount: fee.amount,
+				});
+			}
+
+			if let Some(usage) = usage {
+				ImportUsages::<T>::insert(to_game, from_game, usage);
+			}
+			GameItems::<T>::remove((from_game, item_id));
+			RentalListings::<T>::remove((from_game, item_id));
+			Approvals::<T>::remove((from_game, item_id));
+			ItemClasses::<T>::remove((from_game, item_id));
+			ItemAttributes::<T>::remove((from_game, item_id));
+			ItemsByOwner::<T>::remove(owner, (from_game, item_id));
+			GameItems::<T>::insert((to_game, item_id), owner.clone());
+			ItemsByOwner::<T>::insert(owner, (to_game, item_id), ());
+			ItemClasses::<T>::insert((to_game, item_id), class_id);
+			ItemAttributes::<T>::insert((to_game, item_id), attributes_after.clone());
+			Ok((attributes_before, attributes_after))
+		}
+
+		/// `to_game`'s import usage for items from `from_game` once one more item is let in, starting
+		/// a fresh count when a new quota period has begun. `None` when no quota applies.
+		fn count_import(to_game: u32, from_game: u32) -> Result<Option<ImportUsageOf<T>>, DispatchError> {
+			let Some(quota) = ImportQuotas::<T>::get(to_game, from_game) else { return Ok(None) };
+			let now = frame_system::Pallet::<T>::block_number();
+			let period_start = now.saturating_sub(now % quota.period);
+			let mut usage = ImportUsages::<T>::get(to_game, from_game);
+			if usage.period_start != period_start {
+				usage = ImportUsage { period_start, count: 0 };
+			}
+			ensure!(usage.count < quota.max_items, Error::<T>::ImportQuotaExceeded);
+			usage.count += 1;
+			Ok(Some(usage))
+		}
+
+		/// Rewrite `attributes` according to `table`. Attributes without a rule pass through unchanged.
+		///
+		/// Fails if a renamed attribute lands on a key another attribute already holds.
+		fn translate(
+			attributes: &AttributesOf<T>,
+			table: &TranslationTableOf<T>,
+		) -> Result<AttributesOf<T>, DispatchError> {
+			let mut translated = BTreeMap::new();
+			for (key, value) in attributes.iter() {
+				let (key, value) = match table.get(key) {
+					None => (key.clone(), value.clone()),
+					Some(AttributeTranslation::Drop) => continue,
+					Some(AttributeTranslation::Map { to, numerator, denominator }) => {
+						let value = match value {
+							AttributeValue::U32(value) => AttributeValue::U32(
+								(u64::from(*value) * u64::from(*numerator) / u64::from(*denominator)).saturated_into(),
+							),
+							other => other.clone(),
+						};
+						(to.clone(), value)
+					},
+				};
+				ensure!(translated.insert(key, value).is_none(), Error::<T>::TranslationCollision);
+			}
+			// Translation never adds attributes, so the result fits the same bound as its input.
+			Ok(AttributesOf::<T>::try_from(translated).unwrap_or_default())
+		}
+	}
+
+	impl<T: Config> CrossGameItemUsage for Pallet<T> {
+		type GameItemId = T::GameItemId;
+		type AccountId = T::AccountId;
+		type GameMetadata = GameMetadataOf<T>;
+
+		fn register_game(game_id: u32, owner: Self::AccountId, game_metadata: Self::GameMetadata) -> DispatchResult {
+			ensure!(!Games::<T>::contains_key(game_id), Error::<T>::GameAlreadyRegistered);
+			Games::<T>::insert(game_id, game_metadata);
+			GameOwners::<T>::insert(game_id, owner);
+			Ok(())
+		}
+
+		fn get_game(game_id: u32) -> Option<Self::GameMetadata> {
+			Games::<T>::get(game_id)
+		}
+
+		fn game_owner(game_id: u32) -> Option<Self::AccountId> {
+			GameOwners::<T>::get(game_id)
+		}
+
+		fn transfer_item(item_id: Self::GameItemId, from_game: u32, to_game: u32, owner: Self::AccountId) -> DispatchResult {
+			Self::do_transfer_item(item_id, from_game, to_game, &owner).map(|_| ())
+		}
+
+		fn owner_of(game_id: u32, item_id: Self::GameItemId) -> Option<Self::AccountId> {
+			GameItems::<T>::get((game_id, item_id))
+		}
+
+		fn transfer_ownership(game_id: u32, item_id: Self::GameItemId, from: &Self::AccountId, to: &Self::AccountId) -> DispatchResult {
+			let owner = GameItems::<T>::get((game_id, item_id)).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(owner == *from, Error::<T>::NotItemOwner);
+			ensure!(!Rentals::<T>::contains_key((game_id, item_id)), Error::<T>::ItemRented);
+			GameItems::<T>::insert((game_id, item_id), to);
+			ItemsByOwner::<T>::remove(from, (game_id, item_id));
+			ItemsByOwner::<T>::insert(to, (game_id, item_id), ());
+			// Rental terms and approvals were granted by the previous owner.
+			RentalListings::<T>::remove((game_id, item_id));
+			Approvals::<T>::remove((game_id, item_id));
+			Ok(())
+		}
+
+		fn item_user(game_id: u32, item_id: Self::GameItemId) -> Option<Self::AccountId> {
+			Self::current_user(game_id, item_id)
+		}
+	}
 }
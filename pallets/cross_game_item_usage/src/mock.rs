@@ -0,0 +1,140 @@
+use crate as pallet_cross_game_item_usage;
+use frame_support::{
+	derive_impl, dispatch::DispatchResult, ensure,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use pallet_assets::{AssetManager, AssetMetadata};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DispatchError,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		CrossGameItemUsage: pallet_cross_game_item_usage,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+thread_local! {
+	static ASSETS: RefCell<BTreeMap<u32, AssetMetadata>> = RefCell::new(BTreeMap::new());
+	static ASSET_BALANCES: RefCell<BTreeMap<(u32, u64), u64>> = RefCell::new(BTreeMap::new());
+}
+
+/// Keeps token balances in memory so tests can charge import fees without adding pallet-assets to
+/// the mock runtime.
+pub struct TestAssets;
+impl AssetManager for TestAssets {
+	type AssetId = u32;
+	type AccountId = u64;
+	type Balance = u64;
+
+	fn register_asset(asset_id: u32, metadata: AssetMetadata) -> DispatchResult {
+		ASSETS.with(|assets| assets.borrow_mut().insert(asset_id, metadata));
+		Ok(())
+	}
+
+	fn get_asset(asset_id: u32) -> Option<AssetMetadata> {
+		ASSETS.with(|assets| assets.borrow().get(&asset_id).cloned())
+	}
+
+	fn mint(asset_id: u32, to: u64, amount: u64) -> DispatchResult {
+		ASSET_BALANCES.with(|balances| *balances.borrow_mut().entry((asset_id, to)).or_default() += amount);
+		Ok(())
+	}
+
+	fn burn(asset_id: u32, from: u64, amount: u64) -> DispatchResult {
+		ensure!(Self::balance(asset_id, &from) >= amount, DispatchError::Other("insufficient balance"));
+		ASSET_BALANCES.with(|balances| *balances.borrow_mut().entry((asset_id, from)).or_default() -= amount);
+		Ok(())
+	}
+
+	fn balance(asset_id: u32, who: &u64) -> u64 {
+		ASSET_BALANCES.with(|balances| balances.borrow().get(&(asset_id, *who)).copied().unwrap_or_default())
+	}
+
+	fn transfer(asset_id: u32, from: u64, to: u64, amount: u64) -> DispatchResult {
+		Self::burn(asset_id, from, amount)?;
+		Self::mint(asset_id, to, amount)
+	}
+}
+
+impl pallet_cross_game_item_usage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type GameItemId = u64;
+	type Currency = Balances;
+	type AssetId = u32;
+	type AssetBalance = u64;
+	type Assets = TestAssets;
+	type MaxRentalsPerBlock = ConstU32<2>;
+	type MaxGameNameLength = ConstU32<32>;
+	type MaxAttributeKeyLength = ConstU32<16>;
+	type MaxAttributeStringLength = ConstU32<16>;
+	type MaxAttributes = ConstU32<8>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	ASSETS.with(|assets| assets.borrow_mut().clear());
+	ASSET_BALANCES.with(|balances| balances.borrow_mut().clear());
+	TestAssets::register_asset(1, AssetMetadata::default()).unwrap();
+	for who in [1, 2, 3] {
+		TestAssets::mint(1, who, 1_000).unwrap();
+	}
+	t.into()
+}
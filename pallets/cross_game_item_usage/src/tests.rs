@@ -1,68 +1,556 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate as pallet_cross_game_item_usage;
-    use frame_support::{assert_ok, impl_outer_origin, parameter_types};
-    use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
-    use sp_core::H256;
-
-    impl_outer_origin! {
-        pub enum Origin for Test {}
-    }
-
-    #[derive(Clone, Eq, PartialEq)]
-    pub struct Test;
-    parameter_types! {
-        pub const BlockHashCount: u64 = 250;
-    }
-    impl frame_system::Config for Test {
-        type BaseCallFilter = frame_support::traits::Everything;
-        type BlockWeights = ();
-        type BlockLength = ();
-        type DbWeight = ();
-        type Origin = Origin;
-        type Call = ();
-        type Index = u64;
-        type BlockNumber = u64;
-        type Hash = H256;
-        type Hashing = BlakeTwo256;
-        type AccountId = u64;
-        type Lookup = IdentityLookup<Self::AccountId>;
-        type Header = Header;
-        type Event = ();
-        type BlockHashCount = BlockHashCount;
-        type Version = ();
-        type PalletInfo = PalletInfo;
-        type AccountData = ();
-        type OnNewAccount = ();
-        type OnKilledAccount = ();
-        type SystemWeightInfo = ();
-    }
-
-    impl Config for Test {
-        type Event = ();
-    }
-
-    type CrossGameItemUsageModule = Module<Test>;
-
-    #[test]
-    fn it_registers_a_game() {
-        new_test_ext().execute_with(|| {
-            // Test registration of game
-            assert_ok!(CrossGameItemUsageModule::register_game(Origin::signed(1), 1, GameMetadata { name: b"Test Game".to_vec() }));
-            assert_eq!(CrossGameItemUsageModule::get_game(1), Some(GameMetadata { name: b"Test Game".to_vec() }));
-        });
-    }
-
-    #[test]
-    fn it_transfers_item() {
-        new_test_ext().execute_with(|| {
-            // Test item transfer between games
-            CrossGameItemUsageModule::register_game(Origin::signed(1), 1, GameMetadata { name: b"Test Game".to_vec() }).unwrap();
-            CrossGameItemUsageModule::register_game(Origin::signed(1), 2, GameMetadata { name: b"Test Game 2".to_vec() }).unwrap();
-            CrossGameItemUsageModule::game_items((1, 1)).insert(1, 1);
-            assert_ok!(CrossGameItemUsageModule::transfer_item(Origin::signed(1), 1, 1, 2));
-            assert_eq!(CrossGameItemUsageModule::game_items((2, 1)), 1);
-        });
-    }
+use crate::{
+	mock::*, AttributeKeyOf, AttributeKind, AttributeSchemaOf, AttributeTranslation, AttributeValue, AttributeValueOf,
+	AttributesOf, CrossGameItemUsage as _, Error, Event, GameMetadataOf, ImportUsage, ItemAttributes,
+	TranslationTableOf,
+};
+use std::collections::BTreeMap;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use pallet_assets::AssetManager;
+
+fn metadata(name: &[u8]) -> GameMetadataOf<Test> {
+	GameMetadataOf::<Test> { name: name.to_vec().try_into().unwrap() }
+}
+
+fn key(name: &[u8]) -> AttributeKeyOf<Test> {
+	name.to_vec().try_into().unwrap()
+}
+
+fn attributes(pairs: &[(&[u8], AttributeValueOf<Test>)]) -> AttributesOf<Test> {
+	pairs
+		.iter()
+		.map(|(name, value)| (key(name), value.clone()))
+		.collect::<BTreeMap<_, _>>()
+		.try_into()
+		.unwrap()
+}
+
+fn schema(fields: &[(&[u8], AttributeKind)]) -> AttributeSchemaOf<Test> {
+	fields.iter().map(|(name, kind)| (key(name), kind.clone())).collect::<BTreeMap<_, _>>().try_into().unwrap()
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		CrossGameItemUsage::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn it_registers_a_game() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(1), 1, metadata(b"Test Game")));
+		assert_eq!(CrossGameItemUsage::get_game(1), Some(metadata(b"Test Game")));
+		System::assert_last_event(Event::GameRegistered { game_id: 1, owner: 1 }.into());
+	});
+}
+
+#[test]
+fn it_transfers_item() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(1), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(1), 2, metadata(b"Test Game 2")));
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(1), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(1), 2, 1, 0));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(1), 1, 1, 0, 1, Default::default()));
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 1, 1, 2));
+		assert_eq!(CrossGameItemUsage::game_items((2, 1)), Some(1));
+		assert_eq!(CrossGameItemUsage::game_items((1, 1)), None);
+		assert_eq!(CrossGameItemUsage::items_of(&1), vec![(2, 1)]);
+		System::assert_last_event(
+			Event::ItemTransferred {
+				item_id: 1,
+				from_game: 1,
+				to_game: 2,
+				owner: 1,
+				attributes_before: Default::default(),
+				attributes_after: Default::default(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn it_rents_item_without_transferring_ownership() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+		assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(1), 1, 7, 10, 5));
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 7, 3));
+
+		assert_eq!(CrossGameItemUsage::game_items((1, 7)), Some(1));
+		assert_eq!(CrossGameItemUsage::current_user(1, 7), Some(2));
+		assert_eq!(Balances::free_balance(1), 1_030);
+		assert_eq!(Balances::free_balance(2), 970);
+	});
+}
+
+#[test]
+fn it_returns_rented_item_when_term_ends() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+		assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(1), 1, 7, 10, 5));
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 7, 3));
+
+		run_to_block(3);
+		assert_eq!(CrossGameItemUsage::current_user(1, 7), Some(2));
+		run_to_block(4);
+		assert_eq!(CrossGameItemUsage::current_user(1, 7), Some(1));
+		assert!(CrossGameItemUsage::rentals((1, 7)).is_none());
+		System::assert_last_event(Event::RentalEnded { game_id: 1, item_id: 7, renter: 2 }.into());
+	});
+}
+
+#[test]
+fn it_rejects_invalid_rentals() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+		assert_noop!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 7, 3), Error::<Test>::NotListedForRent);
+		assert_noop!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(2), 1, 7, 10, 5), Error::<Test>::NotItemOwner);
+		assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(1), 1, 7, 10, 5));
+		assert_noop!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 7, 6), Error::<Test>::InvalidRentalDuration);
+		assert_noop!(CrossGameItemUsage::rent(RuntimeOrigin::signed(1), 1, 7, 2), Error::<Test>::RenterIsOwner);
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 7, 2));
+		assert_noop!(CrossGameItemUsage::rent(RuntimeOrigin::signed(3), 1, 7, 2), Error::<Test>::ItemRented);
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2), Error::<Test>::ItemRented);
+	});
+}
+
+#[test]
+fn it_bounds_rentals_ending_in_one_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		for item_id in 1..=3 {
+			assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, item_id, 0, 1, Default::default()));
+			assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(1), 1, item_id, 1, 5));
+		}
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 1, 2));
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 2, 2));
+		assert_noop!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 3, 2), Error::<Test>::TooManyRentalsEnding);
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 3, 3));
+	});
+}
+
+#[test]
+fn operator_mints_and_burns_items() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 2, metadata(b"Test Game 2")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, attributes(&[(b"attack", AttributeValue::U32(5))])));
+		System::assert_last_event(Event::ItemMinted { game_id: 1, item_id: 7, owner: 1 }.into());
+		assert_eq!(CrossGameItemUsage::game_items((1, 7)), Some(1));
+		assert_eq!(CrossGameItemUsage::items_of(&1), vec![(1, 7)]);
+
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(10), 2, 1, 0));
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2));
+		assert_eq!(CrossGameItemUsage::items_of(&1), vec![(2, 7)]);
+		assert_eq!(ItemAttributes::<Test>::get((2, 7)), attributes(&[(b"attack", AttributeValue::U32(5))]));
+
+		assert_ok!(CrossGameItemUsage::burn_item(RuntimeOrigin::signed(10), 2, 7));
+		System::assert_last_event(Event::ItemBurned { game_id: 2, item_id: 7, owner: 1 }.into());
+		assert_eq!(CrossGameItemUsage::game_items((2, 7)), None);
+		assert!(CrossGameItemUsage::items_of(&1).is_empty());
+		assert!(ItemAttributes::<Test>::get((2, 7)).is_empty());
+	});
+}
+
+#[test]
+fn only_operators_mint_and_burn() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()),
+			Error::<Test>::GameNotFound
+		);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_noop!(
+			CrossGameItemUsage::mint_item(RuntimeOrigin::signed(1), 1, 7, 0, 1, Default::default()),
+			Error::<Test>::NotGameOperator
+		);
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+		assert_noop!(
+			CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 2, Default::default()),
+			Error::<Test>::ItemAlreadyExists
+		);
+		assert_noop!(CrossGameItemUsage::burn_item(RuntimeOrigin::signed(1), 1, 7), Error::<Test>::NotGameOperator);
+		assert_noop!(CrossGameItemUsage::burn_item(RuntimeOrigin::signed(10), 1, 8), Error::<Test>::ItemNotFound);
+
+		assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(1), 1, 7, 10, 5));
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(2), 1, 7, 3));
+		assert_noop!(CrossGameItemUsage::burn_item(RuntimeOrigin::signed(10), 1, 7), Error::<Test>::ItemRented);
+	});
+}
+
+#[test]
+fn it_rejects_duplicate_game_ids() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_noop!(
+			CrossGameItemUsage::register_game(RuntimeOrigin::signed(2), 1, metadata(b"Hijacked")),
+			Error::<Test>::GameAlreadyRegistered
+		);
+		assert_eq!(CrossGameItemUsage::game_owners(1), Some(10));
+		assert_eq!(CrossGameItemUsage::get_game(1), Some(metadata(b"Test Game")));
+	});
+}
+
+#[test]
+fn owner_manages_operators() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_noop!(
+			CrossGameItemUsage::update_game_metadata(RuntimeOrigin::signed(11), 1, metadata(b"Renamed")),
+			Error::<Test>::NotGameOperator
+		);
+		assert_noop!(CrossGameItemUsage::add_operator(RuntimeOrigin::signed(11), 1, 11), Error::<Test>::NotGameOwner);
+
+		assert_ok!(CrossGameItemUsage::add_operator(RuntimeOrigin::signed(10), 1, 11));
+		System::assert_last_event(Event::OperatorAdded { game_id: 1, operator: 11 }.into());
+		assert_ok!(CrossGameItemUsage::update_game_metadata(RuntimeOrigin::signed(11), 1, metadata(b"Renamed")));
+		assert_eq!(CrossGameItemUsage::get_game(1), Some(metadata(b"Renamed")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(11), 1, 7, 0, 1, Default::default()));
+		assert_noop!(CrossGameItemUsage::add_operator(RuntimeOrigin::signed(11), 1, 12), Error::<Test>::NotGameOwner);
+
+		assert_ok!(CrossGameItemUsage::remove_operator(RuntimeOrigin::signed(10), 1, 11));
+		System::assert_last_event(Event::OperatorRemoved { game_id: 1, operator: 11 }.into());
+		assert_noop!(CrossGameItemUsage::burn_item(RuntimeOrigin::signed(11), 1, 7), Error::<Test>::NotGameOperator);
+		assert_noop!(CrossGameItemUsage::remove_operator(RuntimeOrigin::signed(10), 1, 11), Error::<Test>::OperatorNotFound);
+		assert_ok!(CrossGameItemUsage::burn_item(RuntimeOrigin::signed(10), 1, 7));
+	});
+}
+
+#[test]
+fn transfers_need_both_games_to_agree() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 3, 1, Default::default()));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 8, 4, 1, Default::default()));
+
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 3), Error::<Test>::GameNotFound);
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2), Error::<Test>::NotExported);
+		assert_noop!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(20), 1, 2, 3), Error::<Test>::NotGameOperator);
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 3));
+		System::assert_last_event(Event::ExportAllowed { game_id: 1, to_game: 2, class_id: 3 }.into());
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2), Error::<Test>::NotAccepted);
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(20), 2, 1, 3));
+		System::assert_last_event(Event::ImportAllowed { game_id: 2, from_game: 1, class_id: 3 }.into());
+
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 8, 1, 2), Error::<Test>::NotExported);
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2));
+		assert_eq!(CrossGameItemUsage::item_classes((2, 7)), 3);
+
+		assert_ok!(CrossGameItemUsage::revoke_import(RuntimeOrigin::signed(20), 2, 1, 3));
+		System::assert_last_event(Event::ImportRevoked { game_id: 2, from_game: 1, class_id: 3 }.into());
+		assert_noop!(
+			CrossGameItemUsage::revoke_import(RuntimeOrigin::signed(20), 2, 1, 3),
+			Error::<Test>::AgreementNotFound
+		);
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 9, 3, 1, Default::default()));
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 9, 1, 2), Error::<Test>::NotAccepted);
+		assert_ok!(CrossGameItemUsage::revoke_export(RuntimeOrigin::signed(10), 1, 2, 3));
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 9, 1, 2), Error::<Test>::NotExported);
+	});
+}
+
+#[test]
+fn it_translates_attributes_into_the_destination_schema() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(20), 2, 1, 0));
+		let before = attributes(&[
+			(b"attack", AttributeValue::U32(5)),
+			(b"durability", AttributeValue::U32(80)),
+			(b"glow", AttributeValue::Bool(true)),
+		]);
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, before.clone()));
+
+		let table: TranslationTableOf<Test> = BTreeMap::from([
+			(key(b"attack"), AttributeTranslation::Map { to: key(b"damage"), numerator: 3, denominator: 1 }),
+			(key(b"durability"), AttributeTranslation::Map { to: key(b"durability"), numerator: 1, denominator: 2 }),
+			(key(b"glow"), AttributeTranslation::Drop),
+		])
+		.try_into()
+		.unwrap();
+		assert_noop!(CrossGameItemUsage::set_translation(RuntimeOrigin::signed(10), 2, 1, table.clone()), Error::<Test>::NotGameOperator);
+		assert_ok!(CrossGameItemUsage::set_translation(RuntimeOrigin::signed(20), 2, 1, table));
+		System::assert_last_event(Event::TranslationSet { game_id: 2, from_game: 1 }.into());
+
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2));
+		let after = attributes(&[(b"damage", AttributeValue::U32(15)), (b"durability", AttributeValue::U32(40))]);
+		assert_eq!(ItemAttributes::<Test>::get((2, 7)), after);
+		System::assert_last_event(
+			Event::ItemTransferred { item_id: 7, from_game: 1, to_game: 2, owner: 1, attributes_before: before, attributes_after: after }
+				.into(),
+		);
+
+		assert_ok!(CrossGameItemUsage::clear_translation(RuntimeOrigin::signed(20), 2, 1));
+		assert_noop!(CrossGameItemUsage::clear_translation(RuntimeOrigin::signed(20), 2, 1), Error::<Test>::TranslationNotFound);
+	});
+}
+
+#[test]
+fn it_rejects_translations_dividing_by_zero() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		let table: TranslationTableOf<Test> =
+			BTreeMap::from([(key(b"attack"), AttributeTranslation::Map { to: key(b"damage"), numerator: 1, denominator: 0 })])
+				.try_into()
+				.unwrap();
+		assert_noop!(CrossGameItemUsage::set_translation(RuntimeOrigin::signed(20), 2, 1, table), Error::<Test>::InvalidTranslation);
+	});
+}
+
+#[test]
+fn it_rejects_translations_that_collide() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(20), 2, 1, 0));
+		let merged: TranslationTableOf<Test> = BTreeMap::from([
+			(key(b"attack"), AttributeTranslation::Map { to: key(b"damage"), numerator: 1, denominator: 1 }),
+			(key(b"power"), AttributeTranslation::Map { to: key(b"damage"), numerator: 1, denominator: 1 }),
+		])
+		.try_into()
+		.unwrap();
+		assert_noop!(CrossGameItemUsage::set_translation(RuntimeOrigin::signed(20), 2, 1, merged), Error::<Test>::TranslationCollision);
+
+		// Renaming onto a key the item already carries would overwrite it.
+		let renamed: TranslationTableOf<Test> =
+			BTreeMap::from([(key(b"attack"), AttributeTranslation::Map { to: key(b"damage"), numerator: 1, denominator: 1 })])
+				.try_into()
+				.unwrap();
+		assert_ok!(CrossGameItemUsage::set_translation(RuntimeOrigin::signed(20), 2, 1, renamed));
+		let sword = attributes(&[(b"attack", AttributeValue::U32(5)), (b"damage", AttributeValue::U32(9))]);
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, sword.clone()));
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2), Error::<Test>::TranslationCollision);
+		assert_eq!(ItemAttributes::<Test>::get((1, 7)), sword);
+	});
+}
+
+#[test]
+fn it_validates_attributes_against_the_game_schema() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		let fields = schema(&[
+			(b"attack", AttributeKind::U32),
+			(b"soulbound", AttributeKind::Bool),
+			(b"title", AttributeKind::String),
+			(b"rarity", AttributeKind::Enum { variants: 3 }),
+		]);
+		assert_noop!(CrossGameItemUsage::set_attribute_schema(RuntimeOrigin::signed(1), 1, fields.clone()), Error::<Test>::NotGameOperator);
+		assert_ok!(CrossGameItemUsage::set_attribute_schema(RuntimeOrigin::signed(10), 1, fields));
+		System::assert_last_event(Event::AttributeSchemaSet { game_id: 1 }.into());
+
+		let sword = attributes(&[
+			(b"attack", AttributeValue::U32(5)),
+			(b"soulbound", AttributeValue::Bool(true)),
+			(b"title", AttributeValue::String(b"Excalibur".to_vec().try_into().unwrap())),
+			(b"rarity", AttributeValue::Enum(2)),
+		]);
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, sword));
+		assert_noop!(
+			CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 8, 0, 1, attributes(&[(b"attack", AttributeValue::Bool(true))])),
+			Error::<Test>::AttributesDoNotMatchSchema
+		);
+		assert_noop!(
+			CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 8, 0, 1, attributes(&[(b"rarity", AttributeValue::Enum(3))])),
+			Error::<Test>::AttributesDoNotMatchSchema
+		);
+		assert_noop!(
+			CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 8, 0, 1, attributes(&[(b"speed", AttributeValue::U32(1))])),
+			Error::<Test>::AttributesDoNotMatchSchema
+		);
+
+		let upgraded = attributes(&[(b"attack", AttributeValue::U32(6)), (b"rarity", AttributeValue::Enum(0))]);
+		assert_noop!(
+			CrossGameItemUsage::update_attributes(RuntimeOrigin::signed(1), 1, 7, upgraded.clone()),
+			Error::<Test>::NotGameOperator
+		);
+		assert_noop!(
+			CrossGameItemUsage::update_attributes(RuntimeOrigin::signed(10), 1, 8, upgraded.clone()),
+			Error::<Test>::ItemNotFound
+		);
+		assert_ok!(CrossGameItemUsage::update_attributes(RuntimeOrigin::signed(10), 1, 7, upgraded.clone()));
+		System::assert_last_event(Event::ItemAttributesUpdated { game_id: 1, item_id: 7, attributes: upgraded.clone() }.into());
+		assert_eq!(ItemAttributes::<Test>::get((1, 7)), upgraded);
+	});
+}
+
+#[test]
+fn it_refuses_transfers_that_break_the_destination_schema() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(20), 2, 1, 0));
+		assert_ok!(CrossGameItemUsage::set_attribute_schema(RuntimeOrigin::signed(20), 2, schema(&[(b"damage", AttributeKind::U32)])));
+		assert_ok!(CrossGameItemUsage::mint_item(
+			RuntimeOrigin::signed(10),
+			1,
+			7,
+			0,
+			1,
+			attributes(&[(b"attack", AttributeValue::U32(5))])
+		));
+
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2), Error::<Test>::AttributesDoNotMatchSchema);
+		let table: TranslationTableOf<Test> =
+			BTreeMap::from([(key(b"attack"), AttributeTranslation::Map { to: key(b"damage"), numerator: 2, denominator: 1 })])
+				.try_into()
+				.unwrap();
+		assert_ok!(CrossGameItemUsage::set_translation(RuntimeOrigin::signed(20), 2, 1, table));
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2));
+		assert_eq!(ItemAttributes::<Test>::get((2, 7)), attributes(&[(b"damage", AttributeValue::U32(10))]));
+	});
+}
+
+#[test]
+fn owners_give_items_to_other_players() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+
+		assert_noop!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(2), 1, 7, 2), Error::<Test>::NotApproved);
+		assert_noop!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(1), 1, 8, 2), Error::<Test>::ItemNotFound);
+		assert_ok!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(1), 1, 7, 2));
+		System::assert_last_event(Event::OwnershipTransferred { game_id: 1, item_id: 7, from: 1, to: 2 }.into());
+		assert_eq!(CrossGameItemUsage::game_items((1, 7)), Some(2));
+		assert!(CrossGameItemUsage::items_of(&1).is_empty());
+		assert_eq!(CrossGameItemUsage::items_of(&2), vec![(1, 7)]);
+
+		assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(2), 1, 7, 10, 5));
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(3), 1, 7, 2));
+		assert_noop!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(2), 1, 7, 1), Error::<Test>::ItemRented);
+		run_to_block(4);
+		assert_ok!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(2), 1, 7, 1));
+		assert!(CrossGameItemUsage::rental_listings((1, 7)).is_none());
+	});
+}
+
+#[test]
+fn approved_accounts_hand_on_items() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Test Game")));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 8, 0, 1, Default::default()));
+
+		assert_noop!(CrossGameItemUsage::approve(RuntimeOrigin::signed(2), 1, 7, 2), Error::<Test>::NotItemOwner);
+		assert_ok!(CrossGameItemUsage::approve(RuntimeOrigin::signed(1), 1, 7, 5));
+		System::assert_last_event(Event::Approved { game_id: 1, item_id: 7, owner: 1, delegate: 5 }.into());
+		assert_noop!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(5), 1, 8, 3), Error::<Test>::NotApproved);
+		assert_ok!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(5), 1, 7, 3));
+		assert_eq!(CrossGameItemUsage::game_items((1, 7)), Some(3));
+		// The approval lapsed with the change of owner.
+		assert_eq!(CrossGameItemUsage::approvals((1, 7)), None);
+		assert_noop!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(5), 1, 7, 5), Error::<Test>::NotApproved);
+
+		assert_ok!(CrossGameItemUsage::approve(RuntimeOrigin::signed(1), 1, 8, 5));
+		assert_ok!(CrossGameItemUsage::cancel_approval(RuntimeOrigin::signed(1), 1, 8));
+		System::assert_last_event(Event::ApprovalCancelled { game_id: 1, item_id: 8, owner: 1 }.into());
+		assert_noop!(CrossGameItemUsage::cancel_approval(RuntimeOrigin::signed(1), 1, 8), Error::<Test>::ApprovalNotFound);
+
+		assert_ok!(CrossGameItemUsage::approve_all(RuntimeOrigin::signed(1), 6, true));
+		System::assert_last_event(Event::ApprovalForAll { owner: 1, delegate: 6, approved: true }.into());
+		assert_ok!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(6), 1, 8, 3));
+		assert_ok!(CrossGameItemUsage::approve_all(RuntimeOrigin::signed(3), 6, false));
+		assert_noop!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(6), 1, 8, 1), Error::<Test>::NotApproved);
+	});
+}
+
+#[test]
+fn destination_game_charges_import_fee() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(20), 2, 1, 0));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+		assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 8, 0, 4, Default::default()));
+
+		assert_noop!(
+			CrossGameItemUsage::set_import_fee(RuntimeOrigin::signed(10), 2, 1, 50, 30),
+			Error::<Test>::NotGameOperator
+		);
+		assert_noop!(CrossGameItemUsage::set_import_fee(RuntimeOrigin::signed(20), 2, 2, 50, 30), Error::<Test>::UnknownAsset);
+		assert_ok!(CrossGameItemUsage::set_import_fee(RuntimeOrigin::signed(20), 2, 1, 50, 30));
+		System::assert_last_event(Event::ImportFeeSet { game_id: 2, asset_id: 1, amount: 50, treasury: 30 }.into());
+
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2));
+		System::assert_has_event(Event::ImportFeePaid { game_id: 2, payer: 1, asset_id: 1, amount: 50 }.into());
+		assert_eq!(TestAssets::balance(1, &1), 950);
+		assert_eq!(TestAssets::balance(1, &30), 50);
+		// Account 4 holds no tokens to pay the fee with.
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(4), 8, 1, 2), Error::<Test>::CannotPayImportFee);
+
+		assert_ok!(CrossGameItemUsage::clear_import_fee(RuntimeOrigin::signed(20), 2));
+		System::assert_last_event(Event::ImportFeeCleared { game_id: 2 }.into());
+		assert_noop!(CrossGameItemUsage::clear_import_fee(RuntimeOrigin::signed(20), 2), Error::<Test>::ImportFeeNotFound);
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(4), 8, 1, 2));
+	});
+}
+
+#[test]
+fn destination_game_limits_imports_per_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata(b"Source")));
+		assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(20), 2, metadata(b"Destination")));
+		assert_ok!(CrossGameItemUsage::allow_export(RuntimeOrigin::signed(10), 1, 2, 0));
+		assert_ok!(CrossGameItemUsage::allow_import(RuntimeOrigin::signed(20), 2, 1, 0));
+		for item_id in [7, 8, 9, 11] {
+			assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, item_id, 0, 1, Default::default()));
+		}
+
+		assert_noop!(
+			CrossGameItemUsage::set_import_quota(RuntimeOrigin::signed(20), 2, 3, 2, 10),
+			Error::<Test>::GameNotFound
+		);
+		assert_noop!(
+			CrossGameItemUsage::set_import_quota(RuntimeOrigin::signed(20), 2, 1, 2, 0),
+			Error::<Test>::InvalidImportQuota
+		);
+		assert_ok!(CrossGameItemUsage::set_import_quota(RuntimeOrigin::signed(20), 2, 1, 2, 10));
+		System::assert_last_event(Event::ImportQuotaSet { game_id: 2, from_game: 1, max_items: 2, period: 10 }.into());
+
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 7, 1, 2));
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 8, 1, 2));
+		assert_noop!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 9, 1, 2), Error::<Test>::ImportQuotaExceeded);
+
+		// A new period starts at block 10 with a fresh count.
+		run_to_block(10);
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 9, 1, 2));
+		assert_eq!(CrossGameItemUsage::import_usages(2, 1), ImportUsage { period_start: 10, count: 1 });
+
+		assert_ok!(CrossGameItemUsage::clear_import_quota(RuntimeOrigin::signed(20), 2, 1));
+		System::assert_last_event(Event::ImportQuotaCleared { game_id: 2, from_game: 1 }.into());
+		assert_noop!(
+			CrossGameItemUsage::clear_import_quota(RuntimeOrigin::signed(20), 2, 1),
+			Error::<Test>::ImportQuotaNotFound
+		);
+		assert_ok!(CrossGameItemUsage::transfer_item(RuntimeOrigin::signed(1), 11, 1, 2));
+	});
 }
@@ -0,0 +1,564 @@
+//! Autogenerated weights for pallet_cross_game_item_usage
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-04-12, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `<UNKNOWN>`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ../../target/release/node-template
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_cross_game_item_usage
+// --extrinsic
+// *
+// --steps=50
+// --repeat=20
+// --wasm-execution=compiled
+// --output
+// pallets/cross_game_item_usage/src/weights.rs
+// --template
+// ../../.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_cross_game_item_usage.
+pub trait WeightInfo {
+	fn register_game() -> Weight;
+	fn transfer_item() -> Weight;
+	fn list_for_rent() -> Weight;
+	fn cancel_rental_listing() -> Weight;
+	fn rent() -> Weight;
+	fn expire_rentals(n: u32, ) -> Weight;
+	fn mint_item() -> Weight;
+	fn burn_item() -> Weight;
+	fn update_game_metadata() -> Weight;
+	fn add_operator() -> Weight;
+	fn remove_operator() -> Weight;
+	fn allow_export() -> Weight;
+	fn revoke_export() -> Weight;
+	fn allow_import() -> Weight;
+	fn revoke_import() -> Weight;
+	fn set_translation() -> Weight;
+	fn clear_translation() -> Weight;
+	fn set_attribute_schema() -> Weight;
+	fn update_attributes() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn approve() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn approve_all() -> Weight;
+	fn set_import_fee() -> Weight;
+	fn clear_import_fee() -> Weight;
+	fn set_import_quota() -> Weight;
+	fn clear_import_quota() -> Weight;
+}
+
+/// Weights for pallet_cross_game_item_usage using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: CrossGameItemUsage Games (r:1 w:1)
+	/// Storage: CrossGameItemUsage GameOwners (r:0 w:1)
+	fn register_game() -> Weight {
+		Weight::from_parts(14_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:2 w:2)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemClasses (r:1 w:2)
+	/// Storage: CrossGameItemUsage Exports (r:1 w:0)
+	/// Storage: CrossGameItemUsage Imports (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:1 w:2)
+	/// Storage: CrossGameItemUsage Translations (r:1 w:0)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	/// Storage: CrossGameItemUsage ImportQuotas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportUsages (r:1 w:1)
+	/// Storage: CrossGameItemUsage ImportFees (r:1 w:0)
+	/// Storage: AssetModule Balances (r:2 w:2)
+	fn transfer_item() -> Weight {
+		Weight::from_parts(91_000_000, 9873)
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	fn list_for_rent() -> Weight {
+		Weight::from_parts(14_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalListings (r:1 w:1)
+	fn cancel_rental_listing() -> Weight {
+		Weight::from_parts(16_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage RentalListings (r:1 w:0)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:1)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalExpiries (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn rent() -> Weight {
+		Weight::from_parts(45_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CrossGameItemUsage RentalExpiries (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:1)
+	/// The range of component `n` is `[0, 16]`.
+	fn expire_rentals(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 3541)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemClasses (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:0 w:1)
+	fn mint_item() -> Weight {
+		Weight::from_parts(34_000_000, 6307)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemClasses (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:0 w:1)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn burn_item() -> Weight {
+		Weight::from_parts(28_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:0 w:1)
+	fn update_game_metadata() -> Weight {
+		Weight::from_parts(15_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:0 w:1)
+	fn add_operator() -> Weight {
+		Weight::from_parts(13_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:1)
+	fn remove_operator() -> Weight {
+		Weight::from_parts(16_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage Exports (r:0 w:1)
+	fn allow_export() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Exports (r:1 w:1)
+	fn revoke_export() -> Weight {
+		Weight::from_parts(18_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage Imports (r:0 w:1)
+	fn allow_import() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Imports (r:1 w:1)
+	fn revoke_import() -> Weight {
+		Weight::from_parts(18_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage Translations (r:0 w:1)
+	fn set_translation() -> Weight {
+		Weight::from_parts(24_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Translations (r:1 w:1)
+	fn clear_translation() -> Weight {
+		Weight::from_parts(19_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:0 w:1)
+	fn set_attribute_schema() -> Weight {
+		Weight::from_parts(21_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:0 w:1)
+	fn update_attributes() -> Weight {
+		Weight::from_parts(31_000_000, 6307)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:1 w:1)
+	/// Storage: CrossGameItemUsage ApprovedForAll (r:1 w:0)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	fn transfer_ownership() -> Weight {
+		Weight::from_parts(32_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_parts(14_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage Approvals (r:1 w:1)
+	fn cancel_approval() -> Weight {
+		Weight::from_parts(16_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage ApprovedForAll (r:0 w:1)
+	fn approve_all() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: AssetModule Assets (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportFees (r:0 w:1)
+	fn set_import_fee() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportFees (r:1 w:1)
+	fn clear_import_fee() -> Weight {
+		Weight::from_parts(16_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportQuotas (r:0 w:1)
+	fn set_import_quota() -> Weight {
+		Weight::from_parts(16_000_000, 3563)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportQuotas (r:1 w:1)
+	/// Storage: CrossGameItemUsage ImportUsages (r:0 w:1)
+	fn clear_import_quota() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: CrossGameItemUsage Games (r:1 w:1)
+	/// Storage: CrossGameItemUsage GameOwners (r:0 w:1)
+	fn register_game() -> Weight {
+		Weight::from_parts(14_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:2 w:2)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemClasses (r:1 w:2)
+	/// Storage: CrossGameItemUsage Exports (r:1 w:0)
+	/// Storage: CrossGameItemUsage Imports (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:1 w:2)
+	/// Storage: CrossGameItemUsage Translations (r:1 w:0)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	/// Storage: CrossGameItemUsage ImportQuotas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportUsages (r:1 w:1)
+	/// Storage: CrossGameItemUsage ImportFees (r:1 w:0)
+	/// Storage: AssetModule Balances (r:2 w:2)
+	fn transfer_item() -> Weight {
+		Weight::from_parts(91_000_000, 9873)
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	fn list_for_rent() -> Weight {
+		Weight::from_parts(14_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalListings (r:1 w:1)
+	fn cancel_rental_listing() -> Weight {
+		Weight::from_parts(16_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage RentalListings (r:1 w:0)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:1)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage RentalExpiries (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn rent() -> Weight {
+		Weight::from_parts(45_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CrossGameItemUsage RentalExpiries (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:1)
+	/// The range of component `n` is `[0, 16]`.
+	fn expire_rentals(n: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 3541)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemClasses (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:0 w:1)
+	fn mint_item() -> Weight {
+		Weight::from_parts(34_000_000, 6307)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemClasses (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:0 w:1)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn burn_item() -> Weight {
+		Weight::from_parts(28_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:0 w:1)
+	fn update_game_metadata() -> Weight {
+		Weight::from_parts(15_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:0 w:1)
+	fn add_operator() -> Weight {
+		Weight::from_parts(13_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:1)
+	fn remove_operator() -> Weight {
+		Weight::from_parts(16_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage Exports (r:0 w:1)
+	fn allow_export() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Exports (r:1 w:1)
+	fn revoke_export() -> Weight {
+		Weight::from_parts(18_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage Imports (r:0 w:1)
+	fn allow_import() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Imports (r:1 w:1)
+	fn revoke_import() -> Weight {
+		Weight::from_parts(18_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage Translations (r:0 w:1)
+	fn set_translation() -> Weight {
+		Weight::from_parts(24_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Translations (r:1 w:1)
+	fn clear_translation() -> Weight {
+		Weight::from_parts(19_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:0 w:1)
+	fn set_attribute_schema() -> Weight {
+		Weight::from_parts(21_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage AttributeSchemas (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemAttributes (r:0 w:1)
+	fn update_attributes() -> Weight {
+		Weight::from_parts(31_000_000, 6307)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:1 w:1)
+	/// Storage: CrossGameItemUsage ApprovedForAll (r:1 w:0)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	fn transfer_ownership() -> Weight {
+		Weight::from_parts(32_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn approve() -> Weight {
+		Weight::from_parts(14_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: CrossGameItemUsage Approvals (r:1 w:1)
+	fn cancel_approval() -> Weight {
+		Weight::from_parts(16_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage ApprovedForAll (r:0 w:1)
+	fn approve_all() -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: AssetModule Assets (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportFees (r:0 w:1)
+	fn set_import_fee() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportFees (r:1 w:1)
+	fn clear_import_fee() -> Weight {
+		Weight::from_parts(16_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage Games (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportQuotas (r:0 w:1)
+	fn set_import_quota() -> Weight {
+		Weight::from_parts(16_000_000, 3563)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOperators (r:1 w:0)
+	/// Storage: CrossGameItemUsage ImportQuotas (r:1 w:1)
+	/// Storage: CrossGameItemUsage ImportUsages (r:0 w:1)
+	fn clear_import_quota() -> Weight {
+		Weight::from_parts(17_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
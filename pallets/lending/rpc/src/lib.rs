@@ -0,0 +1,94 @@
+//! RPC interface for pallet-lending, backed by the `LendingApi` runtime API.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorCode, ErrorObject, ErrorObjectOwned},
+};
+use pallet_lending::{AccountLiquidity, Market, PoolId};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::{ApiError, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{traits::Block as BlockT, FixedU128, Permill};
+
+pub use pallet_lending_runtime_api::LendingApi as LendingRuntimeApi;
+
+#[rpc(client, server)]
+pub trait LendingApi<BlockHash, AccountId, AssetId, Balance> {
+    #[method(name = "lending_accountLiquidity")]
+    fn account_liquidity(&self, pool_id: PoolId, who: AccountId, at: Option<BlockHash>) -> RpcResult<Option<AccountLiquidity<Balance>>>;
+
+    #[method(name = "lending_healthFactor")]
+    fn health_factor(&self, pool_id: PoolId, who: AccountId, at: Option<BlockHash>) -> RpcResult<Option<FixedU128>>;
+
+    #[method(name = "lending_marketState")]
+    fn market_state(&self, pool_id: PoolId, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<Option<Market<Balance>>>;
+
+    #[method(name = "lending_supplyRate")]
+    fn supply_rate(&self, pool_id: PoolId, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<Permill>;
+
+    #[method(name = "lending_borrowRate")]
+    fn borrow_rate(&self, pool_id: PoolId, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<Permill>;
+
+    #[method(name = "lending_accruedYield")]
+    fn accrued_yield(&self, pool_id: PoolId, who: AccountId, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<Balance>;
+}
+
+/// Provides RPC methods to query pallet-lending state.
+pub struct Lending<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Lending<C, Block> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+fn runtime_error(err: ApiError) -> ErrorObjectOwned {
+    ErrorObject::owned(ErrorCode::InternalError.code(), "Unable to query lending state.", Some(err.to_string()))
+}
+
+impl<C, Block, AccountId, AssetId, Balance> LendingApiServer<<Block as BlockT>::Hash, AccountId, AssetId, Balance> for Lending<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: LendingRuntimeApi<Block, AccountId, AssetId, Balance>,
+    AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+    AssetId: Codec + DeserializeOwned + Send + Sync + 'static,
+    Balance: Codec + Serialize + Send + Sync + 'static,
+{
+    fn account_liquidity(&self, pool_id: PoolId, who: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<AccountLiquidity<Balance>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().account_liquidity(at, pool_id, who).map_err(runtime_error)
+    }
+
+    fn health_factor(&self, pool_id: PoolId, who: AccountId, at: Option<Block::Hash>) -> RpcResult<Option<FixedU128>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().health_factor(at, pool_id, who).map_err(runtime_error)
+    }
+
+    fn market_state(&self, pool_id: PoolId, asset_id: AssetId, at: Option<Block::Hash>) -> RpcResult<Option<Market<Balance>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().market_state(at, pool_id, asset_id).map_err(runtime_error)
+    }
+
+    fn supply_rate(&self, pool_id: PoolId, asset_id: AssetId, at: Option<Block::Hash>) -> RpcResult<Permill> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().supply_rate(at, pool_id, asset_id).map_err(runtime_error)
+    }
+
+    fn borrow_rate(&self, pool_id: PoolId, asset_id: AssetId, at: Option<Block::Hash>) -> RpcResult<Permill> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().borrow_rate(at, pool_id, asset_id).map_err(runtime_error)
+    }
+
+    fn accrued_yield(&self, pool_id: PoolId, who: AccountId, asset_id: AssetId, at: Option<Block::Hash>) -> RpcResult<Balance> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client.runtime_api().accrued_yield(at, pool_id, who, asset_id).map_err(runtime_error)
+    }
+}
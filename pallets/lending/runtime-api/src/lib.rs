@@ -0,0 +1,30 @@
+//! Runtime API definition for pallet-lending.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_lending::{AccountLiquidity, Market, PoolId};
+use sp_runtime::{FixedU128, Permill};
+
+sp_api::decl_runtime_apis! {
+    /// Read-only view of account health and market state, so clients need not re-implement the
+    /// pallet's valuation logic.
+    pub trait LendingApi<AccountId, AssetId, Balance> where
+        AccountId: Codec,
+        AssetId: Codec,
+        Balance: Codec,
+    {
+        /// Borrowing headroom or shortfall of `who` in a pool. `None` if a price is unavailable.
+        fn account_liquidity(pool_id: PoolId, who: AccountId) -> Option<AccountLiquidity<Balance>>;
+        /// Liquidation collateral over debt of `who` in a pool. `None` if a price is unavailable.
+        fn health_factor(pool_id: PoolId, who: AccountId) -> Option<FixedU128>;
+        /// Risk parameters and totals of a market, if listed.
+        fn market_state(pool_id: PoolId, asset_id: AssetId) -> Option<Market<Balance>>;
+        /// Per-block rate currently earned by depositors.
+        fn supply_rate(pool_id: PoolId, asset_id: AssetId) -> Permill;
+        /// Per-block rate currently charged to borrowers.
+        fn borrow_rate(pool_id: PoolId, asset_id: AssetId) -> Permill;
+        /// Yield earned by a deposit of `who` and not yet claimed.
+        fn accrued_yield(pool_id: PoolId, who: AccountId, asset_id: AssetId) -> Balance;
+    }
+}
@@ -1,39 +1,154 @@
+//! Benchmarking setup for pallet-lending
 #![cfg(feature = "runtime-benchmarks")]
-
 use super::*;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+
+use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
 
-benchmarks! {
-    deposit {
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), 1, 100u32.into())
-    verify {
-        assert_eq!(Deposits::<T>::get((1, caller)), 100u32.into());
-    }
-
-    withdraw {
-        let caller: T::AccountId = whitelisted_caller();
-        Deposits::<T>::insert((1, caller.clone()), 100u32.into());
-    }: _(RawOrigin::Signed(caller), 1, 50u32.into())
-    verify {
-        assert_eq!(Deposits::<T>::get((1, caller)), 50u32.into());
-    }
-
-    borrow {
-        let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), 1, 100u32.into(), 1u32.into())
-    verify {
-        assert_eq!(Loans::<T>::get((1, caller)), (100u32.into(), 1u32.into()));
-    }
-
-    repay {
-        let caller: T::AccountId = whitelisted_caller();
-        Loans::<T>::insert((1, caller.clone()), (100u32.into(), 1u32.into()));
-    }: _(RawOrigin::Signed(caller), 1, 50u32.into())
-    verify {
-        assert_eq!(Loans::<T>::get((1, caller)), (50u32.into(), 1u32.into()));
-    }
+/// Create pool 0 listing `asset_id`, funded with `amount` of liquidity posted by `who`.
+fn setup_pool<T: Config>(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) -> Result<PoolId, BenchmarkError> {
+	let pool_id = Pallet::<T>::next_pool_id();
+	NextPoolId::<T>::put(pool_id + 1);
+	Pools::<T>::insert(pool_id, true);
+	Markets::<T>::insert(pool_id, asset_id, Market {
+		collateral_factor: Permill::from_percent(50),
+		liquidation_threshold: Permill::from_percent(80),
+		rate_model: RateModel { base_rate: Permill::from_percent(5), slope: Permill::from_percent(10) },
+		reserve_factor: Permill::from_percent(10),
+		total_deposits: Zero::zero(),
+		total_borrows: Zero::zero(),
+		yield_index: Zero::zero(),
+		exchange_rate: FixedU128::one(),
+		epoch: 0,
+	});
+	<Pallet<T> as Lending>::deposit(pool_id, asset_id, who.clone(), amount)?;
+	Ok(pool_id)
 }
 
-impl_benchmark_test_suite!(LendingModule, crate::mock::new_test_ext(), crate::mock::Test);
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_pool() {
+		#[extrinsic_call]
+		_(RawOrigin::Root);
+
+		assert!(Pools::<T>::get(0));
+	}
+
+	#[benchmark]
+	fn set_market() {
+		Pools::<T>::insert(0, true);
+		let rate_model = RateModel { base_rate: Permill::from_percent(5), slope: Permill::from_percent(10) };
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 0, T::AssetId::default(), Permill::from_percent(50), Permill::from_percent(80), rate_model, Permill::from_percent(10));
+
+		assert!(Markets::<T>::get(0, T::AssetId::default()).is_some());
+	}
+
+	#[benchmark]
+	fn deposit() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, Zero::zero())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), pool_id, T::AssetId::default(), 100u32.into());
+
+		assert_eq!(Deposits::<T>::get((pool_id, caller), T::AssetId::default()), 100u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, 100u32.into())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), pool_id, T::AssetId::default(), 50u32.into());
+
+		assert_eq!(Deposits::<T>::get((pool_id, caller), T::AssetId::default()), 50u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_interest() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, 1_000u32.into())?;
+		Pallet::<T>::distribute_interest(pool_id, Default::default(), 100u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), pool_id, T::AssetId::default());
+
+		assert_eq!(Deposits::<T>::get((pool_id, caller), T::AssetId::default()), 1_090u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_reward_speed() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, Zero::zero())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, pool_id, T::AssetId::default(), 10u32.into(), 20u32.into());
+
+		assert_eq!(RewardSpeeds::<T>::get(pool_id, T::AssetId::default()).borrow, 20u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_rewards() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, 1_000u32.into())?;
+		RewardSpeeds::<T>::insert(pool_id, T::AssetId::default(), RewardSpeed { supply: 10u32.into(), borrow: Zero::zero() });
+		T::Assets::mint(T::RewardAssetId::get(), T::RewardAccount::get(), 1_000u32.into())?;
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), pool_id);
+
+		assert!(T::Assets::balance(T::RewardAssetId::get(), &caller) > Zero::zero());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn borrow() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, 1_000u32.into())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), pool_id, T::AssetId::default(), 100u32.into(), 1u32.into(), RateMode::Variable);
+
+		assert_eq!(Loans::<T>::get(pool_id, 0).map(|loan| loan.principal), Some(100u32.into()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn repay() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, 1_000u32.into())?;
+		let loan_id = Pallet::<T>::open_loan(pool_id, Default::default(), &caller, None, 100u32.into(), 1u32.into(), RateMode::Variable)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), pool_id, loan_id, 50u32.into());
+
+		assert_eq!(Loans::<T>::get(pool_id, loan_id).map(|loan| loan.principal), Some(50u32.into()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn repay_all() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let pool_id = setup_pool::<T>(Default::default(), &caller, 1_000u32.into())?;
+		let loan_id = Pallet::<T>::open_loan(pool_id, Default::default(), &caller, None, 100u32.into(), 1u32.into(), RateMode::Variable)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), pool_id, loan_id);
+
+		assert!(Loans::<T>::get(pool_id, loan_id).is_none());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -1,119 +1,2088 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
-use frame_system::ensure_signed;
-use sp_runtime::traits::Saturating;
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::Get,
+	weights::Weight,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_assets::AssetManager;
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Bounded, Saturating, Zero},
+	DispatchError, FixedPointNumber, FixedU128, Permill, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// Source of asset prices, all quoted in the same unit of account.
+pub trait PriceFeed<AssetId> {
+	fn get_price(asset_id: &AssetId) -> Option<FixedU128>;
+}
+
+/// Custody of game items pledged as collateral, provided by the pallet that tracks item ownership.
+pub trait ItemCustody<AccountId, ItemId> {
+	fn owner(item_id: &ItemId) -> Option<AccountId>;
+	fn transfer(item_id: &ItemId, from: &AccountId, to: &AccountId) -> DispatchResult;
+}
+
+/// No items exist, so none can be pledged.
+impl<AccountId, ItemId> ItemCustody<AccountId, ItemId> for () {
+	fn owner(_item_id: &ItemId) -> Option<AccountId> {
+		None
+	}
+
+	fn transfer(_item_id: &ItemId, _from: &AccountId, _to: &AccountId) -> DispatchResult {
+		Err(DispatchError::Other("no item custody configured"))
+	}
+}
+
+/// Identifier of an isolated lending pool. Positions in one pool never back debt in another.
+pub type PoolId = u32;
+
+/// Identifier of an efficiency mode category within a pool.
+pub type EModeCategoryId = u8;
+
+/// Identifier of a single loan within its pool. Each `borrow` opens a new loan with the pool's
+/// next free id.
+pub type LoanId = u64;
+
+/// Identifier of a standing instruction.
+pub type InstructionId = u64;
+
+/// Action a borrower wants taken on their behalf while they are offline.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Instruction<AssetId> {
+	/// Once the pool health factor drops below `threshold`, sell deposits of `collateral_asset`
+	/// to repay `loan_id`.
+	StopLoss { threshold: FixedU128, collateral_asset: AssetId, loan_id: LoanId },
+	/// Repay `loan_id` in full from the borrower's wallet once it reaches maturity. Waits while
+	/// the wallet holds less than is owed.
+	RepayAtMaturity { loan_id: LoanId },
+}
+
+impl<AssetId> Instruction<AssetId> {
+	pub fn loan_id(&self) -> LoanId {
+		match self {
+			Instruction::StopLoss { loan_id, .. } | Instruction::RepayAtMaturity { loan_id } => *loan_id,
+		}
+	}
+}
+
+/// A registered instruction and the account and pool it acts on.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct StandingInstruction<AccountId, AssetId> {
+	pub owner: AccountId,
+	pub pool_id: PoolId,
+	pub instruction: Instruction<AssetId>,
+}
+
+/// Per-block borrow rate as a function of market utilization: `base_rate + slope * utilization`.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+pub struct RateModel {
+	pub base_rate: Permill,
+	pub slope: Permill,
+}
+
+impl RateModel {
+	pub fn borrow_rate(&self, utilization: Permill) -> Permill {
+		self.base_rate.saturating_add(self.slope * utilization)
+	}
+}
+
+/// A lendable asset within a pool, with the pool's risk parameters for it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Market<Balance> {
+	/// Share of a deposit's value that counts toward borrowing power.
+	pub collateral_factor: Permill,
+	/// Share of a deposit's value up to which debt is considered healthy.
+	pub liquidation_threshold: Permill,
+	pub rate_model: RateModel,
+	/// Share of collected interest kept as reserves. The rest is paid to depositors.
+	pub reserve_factor: Permill,
+	pub total_deposits: Balance,
+	pub total_borrows: Balance,
+	/// Cumulative interest paid to depositors per unit deposited since the market was listed.
+	pub yield_index: FixedU128,
+	/// Value of a unit of deposit balance relative to when the epoch began. Starts at one and
+	/// falls whenever bad debt is socialized across depositors.
+	pub exchange_rate: FixedU128,
+	/// Bumped, with `exchange_rate` reset to one, when a loss wipes out every deposit.
+	pub epoch: u32,
+}
+
+impl<Balance: Default> Default for Market<Balance> {
+	fn default() -> Self {
+		Market {
+			collateral_factor: Default::default(),
+			liquidation_threshold: Default::default(),
+			rate_model: Default::default(),
+			reserve_factor: Default::default(),
+			total_deposits: Default::default(),
+			total_borrows: Default::default(),
+			yield_index: Default::default(),
+			exchange_rate: FixedU128::one(),
+			epoch: 0,
+		}
+	}
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> Market<Balance> {
+	/// Fraction of deposits currently lent out.
+	pub fn utilization(&self) -> Permill {
+		if self.total_deposits.is_zero() {
+			return Permill::zero();
+		}
+		Permill::from_rational(self.total_borrows.min(self.total_deposits), self.total_deposits)
+	}
+
+	/// Deposits not currently lent out.
+	pub fn available_liquidity(&self) -> Balance {
+		self.total_deposits.saturating_sub(self.total_borrows)
+	}
+}
+
+/// Reward tokens a market emits per block, shared pro rata among its suppliers and among its
+/// borrowers respectively.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RewardSpeed<Balance> {
+	pub supply: Balance,
+	pub borrow: Balance,
+}
+
+/// Cumulative rewards emitted per unit supplied and per unit borrowed in a market.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RewardIndex<Moment> {
+	pub supply: FixedU128,
+	pub borrow: FixedU128,
+	/// Block the indexes were last brought up to.
+	pub updated_at: Moment,
+}
+
+/// Boosted risk parameters for a group of correlated assets. An account that opts into the
+/// category uses these instead of the market parameters, but may only borrow category assets.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct EModeCategory {
+	pub collateral_factor: Permill,
+	pub liquidation_threshold: Permill,
+}
+
+/// An account's collateral and debt in one pool, valued in the price feed's unit.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountPosition<Balance> {
+	/// Collateral weighted by collateral factors.
+	pub borrowing_power: Balance,
+	/// Collateral weighted by liquidation thresholds.
+	pub liquidation_collateral: Balance,
+	/// Outstanding principal plus accrued interest.
+	pub debt: Balance,
+}
+
+/// Headroom of an account's borrowing power over its debt in one pool. At most one of the two
+/// fields is non-zero.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountLiquidity<Balance> {
+	/// How much more value the account could borrow.
+	pub liquidity: Balance,
+	/// How far debt exceeds borrowing power.
+	pub shortfall: Balance,
+}
+
+/// How a loan's interest rate is determined.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum RateMode {
+	/// Follows the market's rate model as utilization changes.
+	Variable,
+	/// Locked at origination and only changed by `rebalance_stable_rate`.
+	Stable,
+}
+
+impl Default for RateMode {
+	fn default() -> Self {
+		RateMode::Variable
+	}
+}
+
+/// An outstanding loan. Interest is accrued lazily whenever the loan is touched.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Loan<AccountId, AssetId, Balance, Moment> {
+	pub borrower: AccountId,
+	pub asset_id: AssetId,
+	/// Principal still owed.
+	pub principal: Balance,
+	/// Interest accrued up to `accrued_at` and not yet paid.
+	pub interest: Balance,
+	/// Block at which the loan was opened.
+	pub start: Moment,
+	/// Term of the loan.
+	pub duration: Moment,
+	/// Block at which `interest` was last brought up to date.
+	pub accrued_at: Moment,
+	/// Account whose collateral backs the loan when it was taken out under a credit delegation.
+	pub delegator: Option<AccountId>,
+	pub rate_mode: RateMode,
+	/// Per-block rate locked for a `Stable` loan. Unused for `Variable` loans.
+	pub stable_rate: Permill,
+}
+
+impl<AccountId, AssetId, Balance, Moment: Saturating + Copy> Loan<AccountId, AssetId, Balance, Moment> {
+	/// Account whose collateral backs the loan and is seized on liquidation.
+	pub fn backer(&self) -> &AccountId {
+		self.delegator.as_ref().unwrap_or(&self.borrower)
+	}
+
+	/// Block at which the loan term ends.
+	pub fn maturity(&self) -> Moment {
+		self.start.saturating_add(self.duration)
+	}
+}
+
+/// Terms of a replacement loan offered to `refinance`.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LoanTerms<Balance, Moment> {
+	/// Principal of the new loan. Must cover everything owed on the loan being replaced.
+	pub amount: Balance,
+	pub duration: Moment,
+}
+
+/// Value and risk parameters assigned to a game item that has no reliable market price.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ItemAppraisal<Balance> {
+	/// Appraised value in the price feed's unit.
+	pub value: Balance,
+	pub collateral_factor: Permill,
+	pub liquidation_threshold: Permill,
+}
+
+/// A declining-price sale of a pledged item to repay one of its owner's loans.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Auction<AccountId, AssetId, Balance, Moment> {
+	/// Account that pledged the item and receives any surplus.
+	pub owner: AccountId,
+	pub loan_id: LoanId,
+	/// Asset of the loan; proceeds are paid in it.
+	pub asset_id: AssetId,
+	/// Prices are in the price feed's unit.
+	pub start_price: Balance,
+	pub floor_price: Balance,
+	pub started_at: Moment,
+}
+
+impl<AccountId, AssetId, Balance: AtLeast32BitUnsigned + Copy, Moment: AtLeast32BitUnsigned + Copy + Into<Balance>> Auction<AccountId, AssetId, Balance, Moment> {
+	/// Price at `now`, falling by `decay` of the starting price each block down to the floor.
+	pub fn price_at(&self, now: Moment, decay: Permill) -> Balance {
+		let drop = (decay * self.start_price).saturating_mul(now.saturating_sub(self.started_at).into());
+		self.start_price.saturating_sub(drop).max(self.floor_price)
+	}
+}
+
+pub type LoanOf<T> = Loan<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, <T as Config>::Balance, <T as Config>::Moment>;
+pub type StandingInstructionOf<T> = StandingInstruction<<T as frame_system::Config>::AccountId, <T as Config>::AssetId>;
+pub type AuctionOf<T> = Auction<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, <T as Config>::Balance, <T as Config>::Moment>;
 
 pub trait Lending {
-    type AssetId;
-    type AccountId;
-    type Balance;
-    type Moment;
-
-    fn deposit(asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult;
-    fn withdraw(asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult;
-    fn borrow(asset_id: Self::AssetId, borrower: Self::AccountId, amount: Self::Balance, duration: Self::Moment) -> dispatch::DispatchResult;
-    fn repay(asset_id: Self::AssetId, borrower: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult;
-    fn calculate_interest(asset_id: Self::AssetId, amount: Self::Balance, duration: Self::Moment) -> Self::Balance;
-}
-
-decl_storage! {
-    trait Store for Module<T: Config> as LendingModule {
-        pub Deposits get(fn deposits): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::Balance;
-        pub Loans get(fn loans): map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => (T::Balance, T::Moment);
-    }
-}
-
-decl_module! {
-    pub struct Module<T: Config> for enum Call where origin: T::Origin {
-        fn deposit(origin, asset_id: T::AssetId, amount: T::Balance) -> dispatch::DispatchResult {
-            let from = ensure_signed(origin)?;
-            let new_balance = Self::deposits((asset_id, from.clone())).saturating_add(amount);
-            <Deposits<T>>::insert((asset_id, from.clone()), new_balance);
-            Self::deposit_event(RawEvent::Deposited(asset_id, from, amount));
-            Ok(())
-        }
-
-        fn withdraw(origin, asset_id: T::AssetId, amount: T::Balance) -> dispatch::DispatchResult {
-            let to = ensure_signed(origin)?;
-            let current_balance = Self::deposits((asset_id, to.clone()));
-            let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
-            <Deposits<T>>::insert((asset_id, to.clone()), new_balance);
-            Self::deposit_event(RawEvent::Withdrawn(asset_id, to, amount));
-            Ok(())
-        }
-
-        fn borrow(origin, asset_id: T::AssetId, amount: T::Balance, duration: T::Moment) -> dispatch::DispatchResult {
-            let borrower = ensure_signed(origin)?;
-            let new_loan = (amount, duration);
-            <Loans<T>>::insert((asset_id, borrower.clone()), new_loan);
-            Self::deposit_event(RawEvent::Borrowed(asset_id, borrower, amount, duration));
-            Ok(())
-        }
-
-        fn repay(origin, asset_id: T::AssetId, amount: T::Balance) -> dispatch::DispatchResult {
-            let borrower = ensure_signed(origin)?;
-            let (current_loan, duration) = Self::loans((asset_id, borrower.clone()));
-            let new_loan = current_loan.checked_sub(&amount).ok_or(Error::<T>::InsufficientLoan)?;
-            <Loans<T>>::insert((asset_id, borrower.clone()), (new_loan, duration));
-            Self::deposit_event(RawEvent::Repaid(asset_id, borrower, amount));
-            Ok(())
-        }
-    }
-}
-
-decl_event!(
-    pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId, AssetId = <T as Config>::AssetId, Balance = <T as Config>::Balance, Moment = <T as Config>::Moment {
-        Deposited(AssetId, AccountId, Balance),
-        Withdrawn(AssetId, AccountId, Balance),
-        Borrowed(AssetId, AccountId, Balance, Moment),
-        Repaid(AssetId, AccountId, Balance),
-    }
-);
-
-decl_error! {
-    pub enum Error for Module<T: Config> {
-        InsufficientBalance,
-        InsufficientLoan,
-    }
-}
-
-impl<T: Config> Lending for Module<T> {
-    type AssetId = T::AssetId;
-    type AccountId = T::AccountId;
-    type Balance = T::Balance;
-    type Moment = T::Moment;
-
-    fn deposit(asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult {
-        let new_balance = Self::deposits((asset_id, from.clone())).saturating_add(amount);
-        <Deposits<T>>::insert((asset_id, from.clone()), new_balance);
-        Ok(())
-    }
-
-    fn withdraw(asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult {
-        let current_balance = Self::deposits((asset_id, to.clone()));
-        let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
-        <Deposits<T>>::insert((asset_id, to.clone()), new_balance);
-        Ok(())
-    }
-
-    fn borrow(asset_id: Self::AssetId, borrower: Self::AccountId, amount: Self::Balance, duration: Self::Moment) -> dispatch::DispatchResult {
-        let new_loan = (amount, duration);
-        <Loans<T>>::insert((asset_id, borrower.clone()), new_loan);
-        Ok(())
-    }
-
-    fn repay(asset_id: Self::AssetId, borrower: Self::AccountId, amount: Self::Balance) -> dispatch::DispatchResult {
-        let (current_loan, duration) = Self::loans((asset_id, borrower.clone()));
-        let new_loan = current_loan.checked_sub(&amount).ok_or(Error::<T>::InsufficientLoan)?;
-        <Loans<T>>::insert((asset_id, borrower.clone()), (new_loan, duration));
-        Ok(())
-    }
-
-    fn calculate_interest(asset_id: Self::AssetId, amount: Self::Balance, duration: Self::Moment) -> Self::Balance {
-        // Implement interest calculation logic here
-        // For simplicity, we'll use a fixed rate of 5% per duration unit
-        let rate: Self::Balance = 5.into();
-        amount.saturating_mul(rate).saturating_mul(duration.into())
-    }
+	type AssetId;
+	type AccountId;
+	type Balance;
+	type Moment;
+
+	fn deposit(pool_id: PoolId, asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> DispatchResult;
+	fn withdraw(pool_id: PoolId, asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> DispatchResult;
+	fn borrow(pool_id: PoolId, asset_id: Self::AssetId, borrower: Self::AccountId, amount: Self::Balance, duration: Self::Moment, rate_mode: RateMode) -> Result<LoanId, DispatchError>;
+	fn repay(pool_id: PoolId, loan_id: LoanId, payer: Self::AccountId, amount: Self::Balance) -> DispatchResult;
+	fn repay_all(pool_id: PoolId, loan_id: LoanId, payer: Self::AccountId) -> DispatchResult;
+	fn amount_owed(pool_id: PoolId, loan_id: LoanId) -> Self::Balance;
+	fn loans_of(pool_id: PoolId, borrower: Self::AccountId) -> Vec<LoanId>;
+	fn extend_loan(pool_id: PoolId, loan_id: LoanId, extra_duration: Self::Moment) -> DispatchResult;
+	fn refinance(pool_id: PoolId, loan_id: LoanId, new_offer: LoanTerms<Self::Balance, Self::Moment>) -> Result<LoanId, DispatchError>;
+	fn calculate_interest(pool_id: PoolId, asset_id: Self::AssetId, amount: Self::Balance, duration: Self::Moment) -> Self::Balance;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::{
+		offchain::{SendTransactionTypes, SubmitTransaction},
+		pallet_prelude::*,
+	};
+	use sp_runtime::{FixedPointOperand, Percent};
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+		/// The overarching runtime event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type AssetId: Parameter + Member + MaybeSerializeDeserialize + Default + Copy;
+		type Balance: Parameter + Member + MaybeSerializeDeserialize + AtLeast32BitUnsigned + FixedPointOperand + Default + Copy;
+		/// Loan durations, measured in blocks.
+		type Moment: Parameter + Member + MaybeSerializeDeserialize + AtLeast32BitUnsigned + Default + Copy + From<BlockNumberFor<Self>> + Into<Self::Balance>;
+		/// Fee charged on the outstanding principal when a loan's term is extended.
+		#[pallet::constant]
+		type ExtensionFee: Get<Percent>;
+		/// Prices used to value collateral against debt.
+		type PriceFeed: PriceFeed<Self::AssetId>;
+		/// Origin allowed to create pools and configure their markets.
+		type MarketAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Maximum number of markets listed in one pool. Bounds the calls that visit every market.
+		#[pallet::constant]
+		type MaxMarketsPerPool: Get<u32>;
+		/// Maximum number of standing instructions examined per block in `on_idle`.
+		#[pallet::constant]
+		type MaxInstructionsPerBlock: Get<u32>;
+		/// Maximum number of standing instructions an account may hold.
+		#[pallet::constant]
+		type MaxInstructionsPerAccount: Get<u32>;
+		/// Share of the repaid debt's value seized on top of it from a liquidated borrower.
+		#[pallet::constant]
+		type LiquidationPenalty: Get<Permill>;
+		/// Maximum number of loans liquidated by one unsigned transaction.
+		#[pallet::constant]
+		type MaxLiquidationsPerTx: Get<u32>;
+		/// Minimum number of blocks between two unsigned liquidation transactions.
+		#[pallet::constant]
+		type UnsignedInterval: Get<BlockNumberFor<Self>>;
+		/// Priority of unsigned liquidation transactions in the pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// How far a market's variable rate must move away from a loan's locked stable rate before
+		/// `rebalance_stable_rate` may reset it.
+		#[pallet::constant]
+		type RebalanceThreshold: Get<Permill>;
+		/// Asset ledger that liquidity mining rewards are paid out of.
+		type Assets: AssetManager<AssetId = Self::AssetId, AccountId = Self::AccountId, Balance = Self::Balance>;
+		/// Asset liquidity mining rewards are paid in.
+		#[pallet::constant]
+		type RewardAssetId: Get<Self::AssetId>;
+		/// Account holding the `RewardAssetId` balance that rewards are paid from.
+		#[pallet::constant]
+		type RewardAccount: Get<Self::AccountId>;
+		/// Share of every addition to a market's reserves that goes to its insurance fund instead.
+		#[pallet::constant]
+		type InsuranceFactor: Get<Permill>;
+		/// Game items that may be pledged as collateral.
+		type ItemId: Parameter + Member + MaybeSerializeDeserialize;
+		/// Moves pledged items in and out of escrow.
+		type Items: ItemCustody<Self::AccountId, Self::ItemId>;
+		/// Account pledged items are held by until released or auctioned.
+		#[pallet::constant]
+		type ItemEscrowAccount: Get<Self::AccountId>;
+		/// Account winning bidders pay auction proceeds into.
+		#[pallet::constant]
+		type PoolAccount: Get<Self::AccountId>;
+		/// How far above its appraisal an item's auction price starts.
+		#[pallet::constant]
+		type AuctionStartPremium: Get<Permill>;
+		/// Share of the starting price an auction's price falls by each block.
+		#[pallet::constant]
+		type AuctionPriceDecay: Get<Permill>;
+		/// Origin allowed to set the floor below which auction prices never fall.
+		type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_pool_id)]
+	pub type NextPoolId<T: Config> = StorageValue<_, PoolId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub type Pools<T: Config> = StorageMap<_, Twox64Concat, PoolId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn markets)]
+	pub type Markets<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AssetId, Market<T::Balance>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn deposits)]
+	pub type Deposits<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
+
+	/// Market `yield_index` as of the last time each deposit's yield was brought up to date.
+	#[pallet::storage]
+	#[pallet::getter(fn yield_indexes)]
+	pub type YieldIndexes<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::AssetId, FixedU128, ValueQuery>;
+
+	/// Market epoch and exchange rate as of the last time each deposit was written down.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_rates)]
+	pub type DepositRates<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::AssetId, (u32, FixedU128), ValueQuery>;
+
+	/// Yield earned by each deposit and not yet claimed.
+	#[pallet::storage]
+	#[pallet::getter(fn accrued_yield_of)]
+	pub type AccruedYield<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
+
+	/// Next free loan id in each pool.
+	#[pallet::storage]
+	#[pallet::getter(fn next_loan_id)]
+	pub type NextLoanId<T: Config> = StorageMap<_, Twox64Concat, PoolId, LoanId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn loans)]
+	pub type Loans<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, LoanId, LoanOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn loans_by_borrower)]
+	pub type LoansByBorrower<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Twox64Concat, LoanId, ()>;
+
+	/// Loans taken out by delegatees against an account's collateral.
+	#[pallet::storage]
+	#[pallet::getter(fn delegated_loans)]
+	pub type DelegatedLoans<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Twox64Concat, LoanId, ()>;
+
+	/// Remaining amount a delegatee may borrow against a delegator's collateral.
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, (T::AccountId, T::AssetId), T::Balance, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn loans_by_asset)]
+	pub type LoansByAsset<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AssetId), Twox64Concat, LoanId, ()>;
+
+	/// Interest collected from borrowers, per pool and asset.
+	#[pallet::storage]
+	#[pallet::getter(fn reserves)]
+	pub type Reserves<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
+
+	/// Funds set aside to cover bad debt before depositors bear it, per pool and asset.
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_fund)]
+	pub type InsuranceFund<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AssetId, T::Balance, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_appraisals)]
+	pub type ItemAppraisals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::ItemId, ItemAppraisal<T::Balance>>;
+
+	/// Items each account has pledged in a pool, including ones currently at auction.
+	#[pallet::storage]
+	#[pallet::getter(fn item_collateral)]
+	pub type ItemCollateral<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::ItemId, ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::ItemId, AuctionOf<T>>;
+
+	/// Share of its appraisal below which an item's auction price never falls. Items cannot be
+	/// auctioned in a pool without one.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_floor)]
+	pub type AuctionFloor<T: Config> = StorageMap<_, Twox64Concat, PoolId, Permill>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn emode_categories)]
+	pub type EModeCategories<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, EModeCategoryId, EModeCategory>;
+
+	/// Category each asset belongs to, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_emode_category)]
+	pub type AssetEModeCategory<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AssetId, EModeCategoryId>;
+
+	/// Category each account has opted into, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn account_emode)]
+	pub type AccountEMode<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AccountId, EModeCategoryId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_instruction_id)]
+	pub type NextInstructionId<T: Config> = StorageValue<_, InstructionId, ValueQuery>;
+
+	/// Standing instructions of every pool in one queue, so that a single cursor can work
+	/// through all of them in `on_idle`. Each instruction records the pool it acts on.
+	#[pallet::storage]
+	#[pallet::getter(fn instructions)]
+	pub type Instructions<T: Config> = StorageMap<_, Twox64Concat, InstructionId, StandingInstructionOf<T>>;
+
+	/// Instructions each account holds across all pools, capped at `MaxInstructionsPerAccount`.
+	#[pallet::storage]
+	#[pallet::getter(fn instruction_count)]
+	pub type InstructionCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Next instruction id `on_idle` will examine. Wraps around once it reaches `NextInstructionId`.
+	#[pallet::storage]
+	#[pallet::getter(fn instruction_cursor)]
+	pub type InstructionCursor<T: Config> = StorageValue<_, InstructionId, ValueQuery>;
+
+	/// Earliest block at which the next unsigned liquidation transaction is accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn next_unsigned_at)]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reward_speeds)]
+	pub type RewardSpeeds<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AssetId, RewardSpeed<T::Balance>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn reward_indexes)]
+	pub type RewardIndexes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AssetId, RewardIndex<T::Moment>, ValueQuery>;
+
+	/// Market supply reward index as of the last time each deposit's rewards were brought up to date.
+	#[pallet::storage]
+	#[pallet::getter(fn supplier_reward_indexes)]
+	pub type SupplierRewardIndexes<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::AssetId, FixedU128, ValueQuery>;
+
+	/// Market borrow reward index as of the last time each borrower's rewards were brought up to date.
+	#[pallet::storage]
+	#[pallet::getter(fn borrower_reward_indexes)]
+	pub type BorrowerRewardIndexes<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (PoolId, T::AccountId), Blake2_128Concat, T::AssetId, FixedU128, ValueQuery>;
+
+	/// Liquidity mining rewards earned by each account in a pool and not yet paid out.
+	#[pallet::storage]
+	#[pallet::getter(fn accrued_rewards)]
+	pub type AccruedRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Markets to list, creating their pools as needed. \[pool_id, asset_id, collateral_factor, liquidation_threshold, rate_model, reserve_factor\]
+		pub markets: Vec<(PoolId, T::AssetId, Permill, Permill, RateModel, Permill)>,
+		/// \[pool_id, asset_id, amount\]
+		pub reserves: Vec<(PoolId, T::AssetId, T::Balance)>,
+		/// Liquidity supplied from the first block. \[pool_id, who, asset_id, amount\]
+		pub deposits: Vec<(PoolId, T::AccountId, T::AssetId, T::Balance)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			for (pool_id, asset_id, collateral_factor, liquidation_threshold, rate_model, reserve_factor) in &self.markets {
+				assert!(collateral_factor <= liquidation_threshold, "collateral factor above liquidation threshold");
+				Pools::<T>::insert(pool_id, true);
+				if *pool_id >= NextPoolId::<T>::get() {
+					NextPoolId::<T>::put(pool_id.checked_add(1).expect("pool id overflow"));
+				}
+				assert!(
+					Markets::<T>::contains_key(pool_id, asset_id)
+						|| (Markets::<T>::iter_key_prefix(pool_id).count() as u32) < T::MaxMarketsPerPool::get(),
+					"more markets in a pool than MaxMarketsPerPool"
+				);
+				Markets::<T>::insert(pool_id, asset_id, Market {
+					collateral_factor: *collateral_factor,
+					liquidation_threshold: *liquidation_threshold,
+					rate_model: *rate_model,
+					reserve_factor: *reserve_factor,
+					..Default::default()
+				});
+			}
+			for (pool_id, asset_id, amount) in &self.reserves {
+				assert!(Markets::<T>::contains_key(pool_id, asset_id), "reserves for an unlisted market");
+				Reserves::<T>::insert(pool_id, asset_id, amount);
+			}
+			for (pool_id, who, asset_id, amount) in &self.deposits {
+				<Pallet<T> as Lending>::deposit(*pool_id, *asset_id, who.clone(), *amount).expect("deposit into an unlisted market");
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new isolated pool was created. \[pool_id\]
+		PoolCreated(PoolId),
+		/// A market was listed or had its risk parameters changed. \[pool_id, asset_id, collateral_factor, liquidation_threshold\]
+		MarketConfigured(PoolId, T::AssetId, Permill, Permill),
+		/// An efficiency mode category was created or updated. \[pool_id, category_id, collateral_factor, liquidation_threshold\]
+		EModeCategorySet(PoolId, EModeCategoryId, Permill, Permill),
+		/// An asset was moved into or out of an efficiency mode category. \[pool_id, asset_id, category_id\]
+		AssetEModeCategorySet(PoolId, T::AssetId, Option<EModeCategoryId>),
+		/// An account entered or left efficiency mode. \[pool_id, who, category_id\]
+		EModeSet(PoolId, T::AccountId, Option<EModeCategoryId>),
+		/// A credit delegation allowance was set. \[pool_id, delegator, delegatee, asset_id, amount\]
+		DelegationApproved(PoolId, T::AccountId, T::AccountId, T::AssetId, T::Balance),
+		/// A loan was taken out against a delegator's collateral. \[pool_id, loan_id, delegator, delegatee\]
+		DelegatedBorrow(PoolId, LoanId, T::AccountId, T::AccountId),
+		/// A stable loan's locked rate was reset to the market rate. \[pool_id, loan_id, old_rate, new_rate\]
+		StableRateRebalanced(PoolId, LoanId, Permill, Permill),
+		/// \[pool_id, asset_id, who, amount\]
+		Deposited(PoolId, T::AssetId, T::AccountId, T::Balance),
+		/// \[pool_id, asset_id, who, amount\]
+		Withdrawn(PoolId, T::AssetId, T::AccountId, T::Balance),
+		/// Accrued yield was added to a deposit. \[pool_id, asset_id, who, amount\]
+		InterestClaimed(PoolId, T::AssetId, T::AccountId, T::Balance),
+		/// Interest collected from a borrower was split between depositors and reserves. \[pool_id, asset_id, to_depositors, to_reserves\]
+		InterestDistributed(PoolId, T::AssetId, T::Balance, T::Balance),
+		/// A market's reward emissions changed. \[pool_id, asset_id, supply_speed, borrow_speed\]
+		RewardSpeedSet(PoolId, T::AssetId, T::Balance, T::Balance),
+		/// Liquidity mining rewards earned in a pool were paid out. \[pool_id, who, amount\]
+		RewardsClaimed(PoolId, T::AccountId, T::Balance),
+		/// A market's insurance fund grew. \[pool_id, asset_id, amount\]
+		InsuranceFunded(PoolId, T::AssetId, T::Balance),
+		/// A loan left without collateral was closed. The insurance fund covered part of the principal and the rest was socialized; unpaid interest is forgiven. \[pool_id, loan_id, asset_id, principal, interest, covered_by_insurance, socialized\]
+		BadDebtWrittenOff(PoolId, LoanId, T::AssetId, T::Balance, T::Balance, T::Balance, T::Balance),
+		/// A loss was spread across a market's depositors. \[pool_id, asset_id, loss, exchange_rate\]
+		LossSocialized(PoolId, T::AssetId, T::Balance, FixedU128),
+		/// \[pool_id, item_id, value\]
+		ItemAppraised(PoolId, T::ItemId, T::Balance),
+		/// \[pool_id, floor\]
+		AuctionFloorSet(PoolId, Permill),
+		/// \[pool_id, who, item_id\]
+		ItemPledged(PoolId, T::AccountId, T::ItemId),
+		/// \[pool_id, who, item_id\]
+		ItemReleased(PoolId, T::AccountId, T::ItemId),
+		/// \[pool_id, item_id, loan_id, start_price, floor_price\]
+		AuctionStarted(PoolId, T::ItemId, LoanId, T::Balance, T::Balance),
+		/// An auctioned item was sold. Amounts are in the loan's asset; the price is in the price feed's unit. \[pool_id, item_id, bidder, price, repaid, surplus\]
+		AuctionSettled(PoolId, T::ItemId, T::AccountId, T::Balance, T::Balance, T::Balance),
+		/// \[pool_id, item_id\]
+		AuctionCancelled(PoolId, T::ItemId),
+		/// A loan was opened. \[pool_id, loan_id, asset_id, borrower, amount, duration\]
+		Borrowed(PoolId, LoanId, T::AssetId, T::AccountId, T::Balance, T::Moment),
+		/// A loan was (partially) repaid. \[pool_id, loan_id, borrower, payer, interest_paid, principal_paid\]
+		Repaid(PoolId, LoanId, T::AccountId, T::AccountId, T::Balance, T::Balance),
+		/// A loan was repaid in full and removed. \[pool_id, loan_id, borrower\]
+		LoanClosed(PoolId, LoanId, T::AccountId),
+		/// A loan's term was extended once its accrued interest and the fee were paid. \[pool_id, loan_id, extra_duration, fee, new_maturity\]
+		LoanExtended(PoolId, LoanId, T::Moment, T::Balance, T::Moment),
+		/// A loan was paid off by a new one. \[pool_id, old_loan_id, new_loan_id, interest_paid, principal_paid, new_principal\]
+		LoanRefinanced(PoolId, LoanId, LoanId, T::Balance, T::Balance, T::Balance),
+		/// A standing instruction was registered. \[instruction_id, owner\]
+		InstructionSet(InstructionId, T::AccountId),
+		/// A standing instruction was cancelled or its loan no longer exists. \[instruction_id\]
+		InstructionRemoved(InstructionId),
+		/// A standing instruction was carried out and removed. \[instruction_id, collateral_sold, repaid\]
+		InstructionExecuted(InstructionId, T::Balance, T::Balance),
+		/// A loan was liquidated. `None` marks a liquidation by the offchain keeper. \[pool_id, loan_id, liquidator, repaid, seized_value\]
+		Liquidated(PoolId, LoanId, Option<T::AccountId>, T::Balance, T::Balance),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		InsufficientBalance,
+		/// No outstanding loan exists with this id.
+		LoanNotFound,
+		/// Only the borrower may perform this action on the loan.
+		NotLoanBorrower,
+		/// The loan id counter overflowed.
+		LoanIdOverflow,
+		/// An extension must add a non-zero duration.
+		ZeroExtension,
+		/// The refinancing offer does not cover the amount owed on the old loan.
+		InsufficientRefinanceAmount,
+		/// The pool does not exist.
+		PoolNotFound,
+		/// The pool id counter overflowed.
+		PoolIdOverflow,
+		/// The asset is not listed in this pool.
+		MarketNotFound,
+		/// The collateral factor may not exceed the liquidation threshold.
+		InvalidRiskParameters,
+		/// The market does not hold enough unborrowed deposits.
+		InsufficientLiquidity,
+		/// The account's collateral in this pool does not cover the resulting debt.
+		InsufficientCollateral,
+		/// The price feed has no price for an asset involved.
+		PriceUnavailable,
+		/// The efficiency mode category does not exist in this pool.
+		EModeCategoryNotFound,
+		/// The asset is outside the account's efficiency mode category.
+		AssetNotInEModeCategory,
+		/// No standing instruction exists with this id.
+		InstructionNotFound,
+		/// Only the account that registered an instruction may cancel it.
+		NotInstructionOwner,
+		/// The account already holds `MaxInstructionsPerAccount` instructions.
+		TooManyInstructions,
+		/// The pool already lists `MaxMarketsPerPool` markets.
+		TooManyMarkets,
+		/// The instruction id counter overflowed.
+		InstructionIdOverflow,
+		/// The loan is healthy and not yet past maturity.
+		NotLiquidatable,
+		/// The borrow exceeds the allowance the delegator approved.
+		InsufficientDelegation,
+		/// Accounts cannot delegate borrowing power to themselves.
+		SelfDelegation,
+		/// The loan does not have a stable rate.
+		NotStableRate,
+		/// The market rate is still within `RebalanceThreshold` of the locked rate.
+		RebalanceNotNeeded,
+		/// The deposit has no accrued yield to claim.
+		NoInterestToClaim,
+		/// The account has no liquidity mining rewards to claim.
+		NoRewardsToClaim,
+		/// The reward account has nothing left to pay rewards with.
+		RewardsDepleted,
+		/// The market's reserves are smaller than the amount requested.
+		InsufficientReserves,
+		/// The item has no appraisal in this pool.
+		ItemNotAppraised,
+		/// Only the item's owner may pledge it.
+		NotItemOwner,
+		/// The item is not pledged by this account in this pool.
+		ItemNotPledged,
+		/// The item is already up for auction.
+		AuctionInProgress,
+		/// No auction is running for this item.
+		AuctionNotFound,
+		/// The auction's loan is still liquidatable.
+		AuctionStillNeeded,
+		/// Governance has not set an auction floor for this pool.
+		AuctionFloorNotSet,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Work through standing instructions from where the previous block stopped, at most
+		/// `MaxInstructionsPerBlock` of them and only as many as the remaining weight allows. Each
+		/// instruction is charged for the positions its owner holds in the pool, as checking and
+		/// carrying it out values every one of them.
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut used = db_weight.reads_writes(2, 1);
+			if remaining_weight.any_lt(used.saturating_add(Self::instruction_weight(0))) {
+				return Weight::zero();
+			}
+			let end = Self::next_instruction_id();
+			let mut cursor = Self::instruction_cursor();
+			// Never visit the same id twice in one block.
+			for _ in 0..(T::MaxInstructionsPerBlock::get() as InstructionId).min(end) {
+				if cursor >= end {
+					cursor = 0;
+				}
+				let positions = Self::instructions(cursor).map_or(0, |standing| Self::position_count(standing.pool_id, &standing.owner));
+				used = used.saturating_add(db_weight.reads(1 + positions as u64));
+				let execution = Self::instruction_weight(positions);
+				if used.saturating_add(execution).any_gt(remaining_weight) {
+					// Left for a later block with more room; only the lookup above is charged.
+					break;
+				}
+				Self::process_instruction(cursor);
+				cursor += 1;
+				used = used.saturating_add(execution);
+			}
+			InstructionCursor::<T>::put(cursor);
+			used
+		}
+
+		/// Scan for loans that are undercollateralized or overdue and submit an unsigned
+		/// transaction liquidating a batch of them.
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			if now < Self::next_unsigned_at() {
+				return;
+			}
+			let loans = Self::liquidatable_loans(T::MaxLiquidationsPerTx::get() as usize);
+			if loans.is_empty() {
+				return;
+			}
+			// A rejected submission is simply retried by the next block's worker.
+			let call = Call::liquidate_unsigned { block_number: now, loans };
+			let _ = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into());
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a new, empty isolated pool.
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2)))]
+		pub fn create_pool(origin: OriginFor<T>) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			let pool_id = Self::next_pool_id();
+			let next_id = pool_id.checked_add(1).ok_or(Error::<T>::PoolIdOverflow)?;
+			NextPoolId::<T>::put(next_id);
+			Pools::<T>::insert(pool_id, true);
+			Self::deposit_event(Event::PoolCreated(pool_id));
+			Ok(())
+		}
+
+		/// List an asset in a pool, or update the risk parameters of an existing market. A pool
+		/// lists at most `MaxMarketsPerPool` markets.
+		#[pallet::call_index(1)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 1)).saturating_add(T::DbWeight::get().reads(T::MaxMarketsPerPool::get() as u64)))]
+		pub fn set_market(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, collateral_factor: Permill, liquidation_threshold: Permill, rate_model: RateModel, reserve_factor: Permill) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::pools(pool_id), Error::<T>::PoolNotFound);
+			ensure!(collateral_factor <= liquidation_threshold, Error::<T>::InvalidRiskParameters);
+			if !<Markets<T>>::contains_key(pool_id, asset_id) {
+				let listed = <Markets<T>>::iter_key_prefix(pool_id).count() as u32;
+				ensure!(listed < T::MaxMarketsPerPool::get(), Error::<T>::TooManyMarkets);
+			}
+			<Markets<T>>::mutate(pool_id, asset_id, |market| {
+				let market = market.get_or_insert_with(Default::default);
+				market.collateral_factor = collateral_factor;
+				market.liquidation_threshold = liquidation_threshold;
+				market.rate_model = rate_model;
+				market.reserve_factor = reserve_factor;
+			});
+			Self::deposit_event(Event::MarketConfigured(pool_id, asset_id, collateral_factor, liquidation_threshold));
+			Ok(())
+		}
+
+		/// Add the caller's accrued yield on a deposit to the deposit itself, where it earns yield
+		/// in turn and can be withdrawn.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 4)))]
+		pub fn claim_interest(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut market = Self::market(pool_id, asset_id)?;
+			Self::accrue_yield(pool_id, &who, asset_id, &market);
+			let amount = <AccruedYield<T>>::take((pool_id, who.clone()), asset_id);
+			ensure!(!amount.is_zero(), Error::<T>::NoInterestToClaim);
+			market.total_deposits = market.total_deposits.saturating_add(amount);
+			<Markets<T>>::insert(pool_id, asset_id, market);
+			<Deposits<T>>::mutate((pool_id, who.clone()), asset_id, |balance| *balance = balance.saturating_add(amount));
+			Self::deposit_event(Event::InterestClaimed(pool_id, asset_id, who, amount));
+			Ok(())
+		}
+
+		/// Set how many reward tokens a market emits per block to its suppliers and to its
+		/// borrowers. Emissions up to now are accounted at the old speeds.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 2)))]
+		pub fn set_reward_speed(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, supply_speed: T::Balance, borrow_speed: T::Balance) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			Self::market(pool_id, asset_id)?;
+			Self::update_reward_index(pool_id, asset_id);
+			<RewardSpeeds<T>>::insert(pool_id, asset_id, RewardSpeed { supply: supply_speed, borrow: borrow_speed });
+			Self::deposit_event(Event::RewardSpeedSet(pool_id, asset_id, supply_speed, borrow_speed));
+			Ok(())
+		}
+
+		/// Bring the caller's rewards in every market of a pool up to date and pay out everything
+		/// they have accrued in the pool. If the reward account runs short, it pays what it holds
+		/// and the rest stays claimable.
+		#[pallet::call_index(4)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 3)).saturating_add(T::DbWeight::get().reads_writes(6, 3).saturating_mul(T::MaxMarketsPerPool::get() as u64)))]
+		pub fn claim_rewards(origin: OriginFor<T>, pool_id: PoolId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::pools(pool_id), Error::<T>::PoolNotFound);
+			let asset_ids: Vec<_> = <Markets<T>>::iter_prefix(pool_id).map(|(asset_id, _)| asset_id).collect();
+			for asset_id in asset_ids {
+				Self::accrue_supply_rewards(pool_id, &who, asset_id);
+				Self::accrue_borrow_rewards(pool_id, &who, asset_id);
+			}
+			let accrued = Self::accrued_rewards(pool_id, &who);
+			ensure!(!accrued.is_zero(), Error::<T>::NoRewardsToClaim);
+			let reward_asset = T::RewardAssetId::get();
+			let reward_account = T::RewardAccount::get();
+			let paid = accrued.min(T::Assets::balance(reward_asset, &reward_account));
+			ensure!(!paid.is_zero(), Error::<T>::RewardsDepleted);
+			T::Assets::transfer(reward_asset, reward_account, who.clone(), paid)?;
+			<AccruedRewards<T>>::insert(pool_id, &who, accrued.saturating_sub(paid));
+			Self::deposit_event(Event::RewardsClaimed(pool_id, who, paid));
+			Ok(())
+		}
+
+		/// Move part of a market's reserves into its insurance fund.
+		#[pallet::call_index(5)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn fund_insurance(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, amount: T::Balance) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			let reserves = Self::reserves(pool_id, asset_id).checked_sub(&amount).ok_or(Error::<T>::InsufficientReserves)?;
+			<Reserves<T>>::insert(pool_id, asset_id, reserves);
+			Self::credit_insurance(pool_id, asset_id, amount);
+			Ok(())
+		}
+
+		/// Create or update an efficiency mode category in a pool.
+		#[pallet::call_index(6)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn set_emode_category(origin: OriginFor<T>, pool_id: PoolId, category_id: EModeCategoryId, collateral_factor: Permill, liquidation_threshold: Permill) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::pools(pool_id), Error::<T>::PoolNotFound);
+			ensure!(collateral_factor <= liquidation_threshold, Error::<T>::InvalidRiskParameters);
+			EModeCategories::<T>::insert(pool_id, category_id, EModeCategory { collateral_factor, liquidation_threshold });
+			Self::deposit_event(Event::EModeCategorySet(pool_id, category_id, collateral_factor, liquidation_threshold));
+			Ok(())
+		}
+
+		/// Place a listed asset in an efficiency mode category, or remove it with `None`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
+		pub fn set_asset_emode_category(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, category_id: Option<EModeCategoryId>) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			Self::market(pool_id, asset_id)?;
+			match category_id {
+				Some(category_id) => {
+					ensure!(EModeCategories::<T>::contains_key(pool_id, category_id), Error::<T>::EModeCategoryNotFound);
+					<AssetEModeCategory<T>>::insert(pool_id, asset_id, category_id);
+				}
+				None => <AssetEModeCategory<T>>::remove(pool_id, asset_id),
+			}
+			Self::deposit_event(Event::AssetEModeCategorySet(pool_id, asset_id, category_id));
+			Ok(())
+		}
+
+		/// Opt into an efficiency mode category, or leave it with `None`. Entering requires every
+		/// open loan in the pool backed by the caller, including loans delegatees took out against
+		/// its collateral, to be in the category; either way the position must stay healthy.
+		#[pallet::call_index(8)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(6, 1)))]
+		pub fn set_emode(origin: OriginFor<T>, pool_id: PoolId, category_id: Option<EModeCategoryId>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::pools(pool_id), Error::<T>::PoolNotFound);
+			if let Some(category_id) = category_id {
+				ensure!(EModeCategories::<T>::contains_key(pool_id, category_id), Error::<T>::EModeCategoryNotFound);
+				let key = (pool_id, who.clone());
+				for (loan_id, _) in <LoansByBorrower<T>>::iter_prefix(key.clone()).chain(<DelegatedLoans<T>>::iter_prefix(key)) {
+					if let Some(loan) = Self::loans(pool_id, loan_id) {
+						ensure!(Self::asset_emode_category(pool_id, loan.asset_id) == Some(category_id), Error::<T>::AssetNotInEModeCategory);
+					}
+				}
+			}
+			match category_id {
+				Some(category_id) => <AccountEMode<T>>::insert(pool_id, &who, category_id),
+				None => <AccountEMode<T>>::remove(pool_id, &who),
+			}
+			if Self::has_loans(pool_id, &who) {
+				let position = Self::account_position(pool_id, &who)?;
+				ensure!(position.borrowing_power >= position.debt, Error::<T>::InsufficientCollateral);
+			}
+			Self::deposit_event(Event::EModeSet(pool_id, who, category_id));
+			Ok(())
+		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn deposit(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, amount: T::Balance) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			<Self as Lending>::deposit(pool_id, asset_id, from.clone(), amount)?;
+			Self::deposit_event(Event::Deposited(pool_id, asset_id, from, amount));
+			Ok(())
+		}
+
+		/// Withdraw a deposit, provided the pool has the liquidity and the remaining collateral
+		/// still covers the caller's debt in this pool.
+		#[pallet::call_index(10)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 2)))]
+		pub fn withdraw(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, amount: T::Balance) -> DispatchResult {
+			let to = ensure_signed(origin)?;
+			<Self as Lending>::withdraw(pool_id, asset_id, to.clone(), amount)?;
+			Self::deposit_event(Event::Withdrawn(pool_id, asset_id, to, amount));
+			Ok(())
+		}
+
+		/// Open a new loan against the caller's collateral in `pool_id`. A borrower may hold any
+		/// number of loans, each with its own term and rate mode. A stable loan locks the
+		/// market's borrow rate as it stands after the loan is taken out.
+		#[pallet::call_index(11)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 5)))]
+		pub fn borrow(origin: OriginFor<T>, pool_id: PoolId, asset_id: T::AssetId, amount: T::Balance, duration: T::Moment, rate_mode: RateMode) -> DispatchResult {
+			let borrower = ensure_signed(origin)?;
+			let loan_id = <Self as Lending>::borrow(pool_id, asset_id, borrower.clone(), amount, duration, rate_mode)?;
+			Self::deposit_event(Event::Borrowed(pool_id, loan_id, asset_id, borrower, amount, duration));
+			Ok(())
+		}
+
+		/// Allow `delegatee` to borrow up to `amount` of `asset_id` against the caller's collateral
+		/// in `pool_id`. Replaces any previous allowance; zero revokes it.
+		#[pallet::call_index(12)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn approve_delegation(origin: OriginFor<T>, pool_id: PoolId, delegatee: T::AccountId, asset_id: T::AssetId, amount: T::Balance) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			Self::market(pool_id, asset_id)?;
+			ensure!(delegator != delegatee, Error::<T>::SelfDelegation);
+			if amount.is_zero() {
+				<Delegations<T>>::remove((pool_id, delegator.clone()), (delegatee.clone(), asset_id));
+			} else {
+				<Delegations<T>>::insert((pool_id, delegator.clone()), (delegatee.clone(), asset_id), amount);
+			}
+			Self::deposit_event(Event::DelegationApproved(pool_id, delegator, delegatee, asset_id, amount));
+			Ok(())
+		}
+
+		/// Borrow against `delegator`'s collateral within the allowance they approved. The loan
+		/// is the caller's to repay, but counts toward the delegator's position and is liquidated
+		/// out of the delegator's collateral.
+		#[pallet::call_index(13)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(5, 7)))]
+		pub fn borrow_delegated(origin: OriginFor<T>, pool_id: PoolId, delegator: T::AccountId, asset_id: T::AssetId, amount: T::Balance, duration: T::Moment, rate_mode: RateMode) -> DispatchResult {
+			let delegatee = ensure_signed(origin)?;
+			let allowance = Self::delegations((pool_id, delegator.clone()), (delegatee.clone(), asset_id));
+			let remaining = allowance.checked_sub(&amount).ok_or(Error::<T>::InsufficientDelegation)?;
+			Self::ensure_can_borrow(pool_id, &delegator, asset_id, amount)?;
+			let loan_id = Self::open_loan(pool_id, asset_id, &delegatee, Some(&delegator), amount, duration, rate_mode)?;
+			<Delegations<T>>::insert((pool_id, delegator.clone()), (delegatee.clone(), asset_id), remaining);
+			Self::deposit_event(Event::Borrowed(pool_id, loan_id, asset_id, delegatee.clone(), amount, duration));
+			Self::deposit_event(Event::DelegatedBorrow(pool_id, loan_id, delegator, delegatee));
+			Ok(())
+		}
+
+		/// Repay up to `amount` of one of the caller's loans, interest first. Overpayment is
+		/// capped at the amount owed, and a loan repaid in full is closed.
+		#[pallet::call_index(14)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 5)))]
+		pub fn repay(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId, amount: T::Balance) -> DispatchResult {
+			let borrower = ensure_signed(origin)?;
+			Self::ensure_borrower(pool_id, loan_id, &borrower)?;
+			Self::do_repay(pool_id, loan_id, &borrower, Some(amount)).map(|_| ())
+		}
+
+		/// Settle one of the caller's loans in full: principal plus all interest accrued so far.
+		#[pallet::call_index(15)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 5)))]
+		pub fn repay_all(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId) -> DispatchResult {
+			let borrower = ensure_signed(origin)?;
+			Self::ensure_borrower(pool_id, loan_id, &borrower)?;
+			Self::do_repay(pool_id, loan_id, &borrower, None).map(|_| ())
+		}
+
+		/// Repay up to `amount` of someone else's loan on their behalf.
+		#[pallet::call_index(16)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 5)))]
+		pub fn repay_on_behalf(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId, amount: T::Balance) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			Self::do_repay(pool_id, loan_id, &payer, Some(amount)).map(|_| ())
+		}
+
+		/// Push back a loan's maturity by `extra_duration`. The caller pays the interest accrued so
+		/// far plus an `ExtensionFee` on the outstanding principal, in the loan's asset into the
+		/// `PoolAccount`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(6, 6)))]
+		pub fn extend_loan(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId, extra_duration: T::Moment) -> DispatchResult {
+			let borrower = ensure_signed(origin)?;
+			Self::ensure_borrower(pool_id, loan_id, &borrower)?;
+			Self::do_extend_loan(pool_id, loan_id, extra_duration)
+		}
+
+		/// Liquidate a loan whose borrower's health factor is below one, or which is past maturity.
+		/// The caller repays the loan in its asset into the `PoolAccount` and receives the
+		/// borrower's collateral worth the repaid amount plus the `LiquidationPenalty`.
+		#[pallet::call_index(18)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(12, 12)))]
+		pub fn liquidate(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId) -> DispatchResult {
+			let liquidator = ensure_signed(origin)?;
+			Self::do_liquidate(pool_id, loan_id, Some(&liquidator))
+		}
+
+		/// Liquidate a batch of loans found by the offchain worker. The seized collateral is sold
+		/// to repay the loans and the penalty goes to reserves. Loans that have recovered by the
+		/// time the transaction is included are skipped.
+		#[pallet::call_index(19)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(12, 12).saturating_mul(loans.len() as u64)))]
+		pub fn liquidate_unsigned(origin: OriginFor<T>, _block_number: BlockNumberFor<T>, loans: Vec<(PoolId, LoanId)>) -> DispatchResult {
+			ensure_none(origin)?;
+			for (pool_id, loan_id) in loans {
+				let _ = with_transaction(|| match Self::do_liquidate(pool_id, loan_id, None) {
+					Ok(()) => TransactionOutcome::Commit(Ok(())),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				});
+			}
+			let now = <frame_system::Pallet<T>>::block_number();
+			<NextUnsignedAt<T>>::put(now.saturating_add(T::UnsignedInterval::get()));
+			Ok(())
+		}
+
+		/// Set the value and risk parameters of a game item that may be pledged in a pool.
+		#[pallet::call_index(20)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+		pub fn appraise_item(origin: OriginFor<T>, pool_id: PoolId, item_id: T::ItemId, appraisal: ItemAppraisal<T::Balance>) -> DispatchResult {
+			T::MarketAdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::pools(pool_id), Error::<T>::PoolNotFound);
+			ensure!(appraisal.collateral_factor <= appraisal.liquidation_threshold, Error::<T>::InvalidRiskParameters);
+			Self::deposit_event(Event::ItemAppraised(pool_id, item_id.clone(), appraisal.value));
+			<ItemAppraisals<T>>::insert(pool_id, item_id, appraisal);
+			Ok(())
+		}
+
+		/// Set the share of an item's appraisal below which its auction price never falls. The
+		/// floor must be above zero.
+		#[pallet::call_index(21)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn set_auction_floor(origin: OriginFor<T>, pool_id: PoolId, floor: Permill) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			ensure!(Self::pools(pool_id), Error::<T>::PoolNotFound);
+			ensure!(!floor.is_zero(), Error::<T>::InvalidRiskParameters);
+			AuctionFloor::<T>::insert(pool_id, floor);
+			Self::deposit_event(Event::AuctionFloorSet(pool_id, floor));
+			Ok(())
+		}
+
+		/// Pledge an appraised game item as collateral. The item moves into escrow and counts
+		/// toward the caller's borrowing power at its appraised value.
+		#[pallet::call_index(22)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 2)))]
+		pub fn pledge_item(origin: OriginFor<T>, pool_id: PoolId, item_id: T::ItemId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::item_appraisals(pool_id, &item_id).is_some(), Error::<T>::ItemNotAppraised);
+			ensure!(T::Items::owner(&item_id).as_ref() == Some(&who), Error::<T>::NotItemOwner);
+			T::Items::transfer(&item_id, &who, &T::ItemEscrowAccount::get())?;
+			<ItemCollateral<T>>::insert((pool_id, who.clone()), item_id.clone(), ());
+			Self::deposit_event(Event::ItemPledged(pool_id, who, item_id));
+			Ok(())
+		}
+
+		/// Take a pledged item back out of escrow, provided the caller's loans stay covered
+		/// without it.
+		#[pallet::call_index(23)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(8, 2)))]
+		pub fn release_item(origin: OriginFor<T>, pool_id: PoolId, item_id: T::ItemId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<ItemCollateral<T>>::contains_key((pool_id, who.clone()), &item_id), Error::<T>::ItemNotPledged);
+			ensure!(!<Auctions<T>>::contains_key(pool_id, &item_id), Error::<T>::AuctionInProgress);
+			if Self::has_loans(pool_id, &who) {
+				let position = Self::account_position(pool_id, &who)?;
+				let lost_power = Self::item_appraisals(pool_id, &item_id).map_or(Zero::zero(), |appraisal| appraisal.collateral_factor * appraisal.value);
+				ensure!(position.borrowing_power.saturating_sub(lost_power) >= position.debt, Error::<T>::InsufficientCollateral);
+			}
+			<ItemCollateral<T>>::remove((pool_id, who.clone()), &item_id);
+			T::Items::transfer(&item_id, &T::ItemEscrowAccount::get(), &who)?;
+			Self::deposit_event(Event::ItemReleased(pool_id, who, item_id));
+			Ok(())
+		}
+
+		/// Put an item pledged by a liquidatable loan's backer up for auction. The price starts
+		/// `AuctionStartPremium` above the appraisal and falls every block down to the pool's
+		/// auction floor, which must have been set.
+		#[pallet::call_index(24)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(12, 1)))]
+		pub fn auction_item(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId, item_id: T::ItemId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(Self::is_liquidatable(pool_id, &loan), Error::<T>::NotLiquidatable);
+			let owner = loan.backer().clone();
+			ensure!(<ItemCollateral<T>>::contains_key((pool_id, owner.clone()), &item_id), Error::<T>::ItemNotPledged);
+			ensure!(!<Auctions<T>>::contains_key(pool_id, &item_id), Error::<T>::AuctionInProgress);
+			let appraisal = Self::item_appraisals(pool_id, &item_id).ok_or(Error::<T>::ItemNotAppraised)?;
+			let start_price = appraisal.value.saturating_add(T::AuctionStartPremium::get() * appraisal.value);
+			let floor = Self::auction_floor(pool_id).ok_or(Error::<T>::AuctionFloorNotSet)?;
+			let floor_price = floor * appraisal.value;
+			<Auctions<T>>::insert(pool_id, &item_id, Auction { owner, loan_id, asset_id: loan.asset_id, start_price, floor_price, started_at: Self::now() });
+			Self::deposit_event(Event::AuctionStarted(pool_id, item_id, loan_id, start_price, floor_price));
+			Ok(())
+		}
+
+		/// Buy an auctioned item at its current price, paid in the loan's asset into the
+		/// `PoolAccount`. The proceeds repay the loan and any surplus is deposited for the item's
+		/// previous owner.
+		#[pallet::call_index(25)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(18, 16)))]
+		pub fn bid(origin: OriginFor<T>, pool_id: PoolId, item_id: T::ItemId) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+			let auction = Self::auctions(pool_id, &item_id).ok_or(Error::<T>::AuctionNotFound)?;
+			let price = auction.price_at(Self::now(), T::AuctionPriceDecay::get());
+			let proceeds = Self::amount_of(auction.asset_id, price)?;
+			T::Assets::transfer(auction.asset_id, bidder.clone(), T::PoolAccount::get(), proceeds)?;
+			<Auctions<T>>::remove(pool_id, &item_id);
+			<ItemCollateral<T>>::remove((pool_id, auction.owner.clone()), &item_id);
+			T::Items::transfer(&item_id, &T::ItemEscrowAccount::get(), &bidder)?;
+			let (repaid, surplus) = Self::settle_auction(pool_id, &auction, &bidder, proceeds)?;
+			Self::deposit_event(Event::AuctionSettled(pool_id, item_id, bidder, price, repaid, surplus));
+			Ok(())
+		}
+
+		/// End an auction whose loan has been repaid or is no longer liquidatable. The item stays
+		/// pledged.
+		#[pallet::call_index(26)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(10, 1)))]
+		pub fn cancel_auction(origin: OriginFor<T>, pool_id: PoolId, item_id: T::ItemId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let auction = Self::auctions(pool_id, &item_id).ok_or(Error::<T>::AuctionNotFound)?;
+			if let Some(loan) = Self::loans(pool_id, auction.loan_id) {
+				ensure!(!Self::is_liquidatable(pool_id, &loan), Error::<T>::AuctionStillNeeded);
+			}
+			<Auctions<T>>::remove(pool_id, &item_id);
+			Self::deposit_event(Event::AuctionCancelled(pool_id, item_id));
+			Ok(())
+		}
+
+		/// Reset a stable loan's locked rate to the market's current borrow rate once the two have
+		/// drifted more than `RebalanceThreshold` apart. Anyone may call this, so neither side is
+		/// stuck with a rate far from the market.
+		#[pallet::call_index(27)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 1)))]
+		pub fn rebalance_stable_rate(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let mut loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+			ensure!(loan.rate_mode == RateMode::Stable, Error::<T>::NotStableRate);
+			let market_rate = Self::borrow_rate(pool_id, loan.asset_id);
+			let drift = market_rate.max(loan.stable_rate).saturating_sub(market_rate.min(loan.stable_rate));
+			ensure!(drift > T::RebalanceThreshold::get(), Error::<T>::RebalanceNotNeeded);
+
+			// Interest up to now is owed at the old rate.
+			Self::accrue(pool_id, &mut loan);
+			let old_rate = loan.stable_rate;
+			loan.stable_rate = market_rate;
+			<Loans<T>>::insert(pool_id, loan_id, loan);
+			Self::deposit_event(Event::StableRateRebalanced(pool_id, loan_id, old_rate, market_rate));
+			Ok(())
+		}
+
+		/// Register a standing instruction on one of the caller's loans, to be carried out by the
+		/// chain in `on_idle` once its condition is met.
+		#[pallet::call_index(28)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 3)))]
+		pub fn set_instruction(origin: OriginFor<T>, pool_id: PoolId, instruction: Instruction<T::AssetId>) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			Self::ensure_borrower(pool_id, instruction.loan_id(), &owner)?;
+			if let Instruction::StopLoss { collateral_asset, .. } = &instruction {
+				Self::market(pool_id, *collateral_asset)?;
+			}
+			let count = Self::instruction_count(&owner);
+			ensure!(count < T::MaxInstructionsPerAccount::get(), Error::<T>::TooManyInstructions);
+
+			let instruction_id = Self::next_instruction_id();
+			let next_id = instruction_id.checked_add(1).ok_or(Error::<T>::InstructionIdOverflow)?;
+			NextInstructionId::<T>::put(next_id);
+			<InstructionCount<T>>::insert(&owner, count + 1);
+			<Instructions<T>>::insert(instruction_id, StandingInstruction { owner: owner.clone(), pool_id, instruction });
+			Self::deposit_event(Event::InstructionSet(instruction_id, owner));
+			Ok(())
+		}
+
+		#[pallet::call_index(29)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
+		pub fn cancel_instruction(origin: OriginFor<T>, instruction_id: InstructionId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let standing = Self::instructions(instruction_id).ok_or(Error::<T>::InstructionNotFound)?;
+			ensure!(standing.owner == owner, Error::<T>::NotInstructionOwner);
+			Self::remove_instruction(instruction_id, &owner);
+			Self::deposit_event(Event::InstructionRemoved(instruction_id));
+			Ok(())
+		}
+
+		/// Replace a loan with a new one on `new_offer` terms. The new principal pays off the old
+		/// loan in full, interest included; any excess is new borrowing and must be covered by
+		/// collateral. The new loan keeps the old rate mode, and a stable rate is locked afresh.
+		#[pallet::call_index(30)]
+		#[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(6, 10)))]
+		pub fn refinance(origin: OriginFor<T>, pool_id: PoolId, loan_id: LoanId, new_offer: LoanTerms<T::Balance, T::Moment>) -> DispatchResult {
+			let borrower = ensure_signed(origin)?;
+			Self::ensure_borrower(pool_id, loan_id, &borrower)?;
+			Self::do_refinance(pool_id, loan_id, new_offer).map(|_| ())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Only accept a liquidation batch once per `UnsignedInterval`, for the current block or
+		/// earlier, and only if every loan in it can be liquidated right now.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (block_number, loans) = match call {
+				Call::liquidate_unsigned { block_number, loans } => (block_number, loans),
+				_ => return InvalidTransaction::Call.into(),
+			};
+			if *block_number < Self::next_unsigned_at() {
+				return InvalidTransaction::Stale.into();
+			}
+			if *block_number > <frame_system::Pallet<T>>::block_number() {
+				return InvalidTransaction::Future.into();
+			}
+			if loans.is_empty() || loans.len() > T::MaxLiquidationsPerTx::get() as usize {
+				return InvalidTransaction::ExhaustsResources.into();
+			}
+			let all_liquidatable = loans.iter().all(|(pool_id, loan_id)| {
+				Self::loans(*pool_id, *loan_id).map_or(false, |loan| Self::is_liquidatable(*pool_id, &loan))
+			});
+			if !all_liquidatable {
+				return InvalidTransaction::Custom(1).into();
+			}
+
+			ValidTransaction::with_tag_prefix("LendingLiquidation")
+				.priority(T::UnsignedPriority::get())
+				.and_provides(block_number)
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn now() -> T::Moment {
+		<frame_system::Pallet<T>>::block_number().into()
+	}
+
+	fn market(pool_id: PoolId, asset_id: T::AssetId) -> Result<Market<T::Balance>, DispatchError> {
+		Self::markets(pool_id, asset_id).ok_or_else(|| Error::<T>::MarketNotFound.into())
+	}
+
+	/// Current per-block borrow rate of a market.
+	pub fn borrow_rate(pool_id: PoolId, asset_id: T::AssetId) -> Permill {
+		Self::markets(pool_id, asset_id)
+			.map(|market| market.rate_model.borrow_rate(market.utilization()))
+			.unwrap_or_else(Permill::zero)
+	}
+
+	/// Collateral factor and liquidation threshold that apply to `who`'s deposits of an asset,
+	/// boosted when the account is in the asset's efficiency mode category.
+	fn risk_parameters(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId, market: &Market<T::Balance>) -> (Permill, Permill) {
+		Self::account_emode(pool_id, who)
+			.filter(|category_id| Self::asset_emode_category(pool_id, asset_id) == Some(*category_id))
+			.and_then(|category_id| Self::emode_categories(pool_id, category_id))
+			.map(|category| (category.collateral_factor, category.liquidation_threshold))
+			.unwrap_or((market.collateral_factor, market.liquidation_threshold))
+	}
+
+	/// Current per-block rate earned by depositors of a market: the borrow rate spread over all
+	/// deposits, less the reserve factor.
+	pub fn supply_rate(pool_id: PoolId, asset_id: T::AssetId) -> Permill {
+		Self::markets(pool_id, asset_id)
+			.map(|market| {
+				let utilization = market.utilization();
+				let gross = Permill::from_parts(utilization * market.rate_model.borrow_rate(utilization).deconstruct());
+				gross.saturating_sub(market.reserve_factor * gross)
+			})
+			.unwrap_or_else(Permill::zero)
+	}
+
+	/// Yield a deposit has earned and not yet claimed, including interest distributed since it
+	/// was last touched.
+	pub fn accrued_yield(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId) -> T::Balance {
+		let stored = Self::accrued_yield_of((pool_id, who.clone()), asset_id);
+		match Self::markets(pool_id, asset_id) {
+			Some(market) => stored.saturating_add(Self::pending_yield(pool_id, who, asset_id, &market)),
+			None => stored,
+		}
+	}
+
+	fn pending_yield(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId, market: &Market<T::Balance>) -> T::Balance {
+		let growth = market.yield_index.saturating_sub(Self::yield_indexes((pool_id, who.clone()), asset_id));
+		let stored = Self::deposits((pool_id, who.clone()), asset_id);
+		growth.saturating_mul_int(Self::written_down(pool_id, who, asset_id, stored, market))
+	}
+
+	/// An account's deposit in a market after any losses socialized since it last changed.
+	pub fn deposit_balance(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId) -> T::Balance {
+		let stored = Self::deposits((pool_id, who.clone()), asset_id);
+		match Self::markets(pool_id, asset_id) {
+			Some(market) => Self::written_down(pool_id, who, asset_id, stored, &market),
+			None => stored,
+		}
+	}
+
+	/// Scale a stored deposit by how far the market's exchange rate has fallen since it was
+	/// written. Deposits from an earlier epoch were wiped out entirely.
+	fn written_down(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId, stored: T::Balance, market: &Market<T::Balance>) -> T::Balance {
+		if stored.is_zero() {
+			return stored;
+		}
+		let (epoch, rate) = Self::deposit_rates((pool_id, who.clone()), asset_id);
+		if epoch < market.epoch {
+			return Zero::zero();
+		}
+		if rate == market.exchange_rate {
+			return stored;
+		}
+		market.exchange_rate.checked_div(&rate).map_or(stored, |ratio| ratio.saturating_mul_int(stored).min(stored))
+	}
+
+	/// Bank a deposit's pending yield and supply rewards and apply any socialized losses. Must run
+	/// before every change to the deposit's balance so that yield and rewards are always earned
+	/// on the balance that was actually held.
+	fn accrue_yield(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId, market: &Market<T::Balance>) {
+		Self::accrue_supply_rewards(pool_id, who, asset_id);
+		let pending = Self::pending_yield(pool_id, who, asset_id, market);
+		if !pending.is_zero() {
+			<AccruedYield<T>>::mutate((pool_id, who.clone()), asset_id, |accrued| *accrued = accrued.saturating_add(pending));
+		}
+		<YieldIndexes<T>>::insert((pool_id, who.clone()), asset_id, market.yield_index);
+		let stored = Self::deposits((pool_id, who.clone()), asset_id);
+		let balance = Self::written_down(pool_id, who, asset_id, stored, market);
+		if balance != stored {
+			<Deposits<T>>::insert((pool_id, who.clone()), asset_id, balance);
+		}
+		<DepositRates<T>>::insert((pool_id, who.clone()), asset_id, (market.epoch, market.exchange_rate));
+	}
+
+	fn touch_deposit(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId) {
+		if let Some(market) = Self::markets(pool_id, asset_id) {
+			Self::accrue_yield(pool_id, who, asset_id, &market);
+		}
+	}
+
+	/// Split interest collected on a market between reserves and depositors, pro rata to their
+	/// deposits. Anything too small to raise the index, or with no depositors to pay, goes to
+	/// reserves so that depositor yield plus reserves always equals the interest collected.
+	fn distribute_interest(pool_id: PoolId, asset_id: T::AssetId, interest: T::Balance) {
+		if interest.is_zero() {
+			return;
+		}
+		let mut to_depositors = Zero::zero();
+		<Markets<T>>::mutate(pool_id, asset_id, |market| {
+			let market = match market {
+				Some(market) if !market.total_deposits.is_zero() => market,
+				_ => return,
+			};
+			let share = interest.saturating_sub(market.reserve_factor * interest);
+			let growth = FixedU128::checked_from_rational(share, market.total_deposits).unwrap_or_else(Zero::zero);
+			market.yield_index = market.yield_index.saturating_add(growth);
+			// Only what the rounded-down index actually pays out counts as depositor yield.
+			to_depositors = growth.saturating_mul_int(market.total_deposits).min(share);
+		});
+		// The reserve factor's cut plus the rounding remainder of the depositors' share.
+		let to_reserves = interest.saturating_sub(to_depositors);
+		Self::add_reserves(pool_id, asset_id, to_reserves);
+		Self::deposit_event(Event::InterestDistributed(pool_id, asset_id, to_depositors, to_reserves));
+	}
+
+	/// Add to a market's reserves, diverting `InsuranceFactor` of the amount to its insurance fund.
+	fn add_reserves(pool_id: PoolId, asset_id: T::AssetId, amount: T::Balance) {
+		let insured = T::InsuranceFactor::get() * amount;
+		Self::credit_insurance(pool_id, asset_id, insured);
+		<Reserves<T>>::mutate(pool_id, asset_id, |reserves| *reserves = reserves.saturating_add(amount.saturating_sub(insured)));
+	}
+
+	fn credit_insurance(pool_id: PoolId, asset_id: T::AssetId, amount: T::Balance) {
+		if amount.is_zero() {
+			return;
+		}
+		<InsuranceFund<T>>::mutate(pool_id, asset_id, |fund| *fund = fund.saturating_add(amount));
+		Self::deposit_event(Event::InsuranceFunded(pool_id, asset_id, amount));
+	}
+
+	/// Close a loan whose backer has no collateral left in the pool. The market's insurance fund
+	/// covers as much of the outstanding principal as it can and the rest is socialized across
+	/// the market's depositors by lowering its exchange rate. Unpaid interest is forgiven, as it
+	/// was never credited to depositors.
+	fn write_off(pool_id: PoolId, loan_id: LoanId) -> DispatchResult {
+		let mut loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+		Self::accrue(pool_id, &mut loan);
+		let asset_id = loan.asset_id;
+		Self::accrue_borrow_rewards(pool_id, &loan.borrower, asset_id);
+		let mut market = Self::market(pool_id, asset_id)?;
+		market.total_borrows = market.total_borrows.saturating_sub(loan.principal);
+
+		let fund = Self::insurance_fund(pool_id, asset_id);
+		let covered = fund.min(loan.principal);
+		<InsuranceFund<T>>::insert(pool_id, asset_id, fund.saturating_sub(covered));
+		let socialized = loan.principal.saturating_sub(covered);
+		if !socialized.is_zero() {
+			let remaining = market.total_deposits.saturating_sub(socialized);
+			if remaining.is_zero() {
+				market.exchange_rate = FixedU128::one();
+				market.epoch = market.epoch.saturating_add(1);
+			} else {
+				let ratio = FixedU128::checked_from_rational(remaining, market.total_deposits).unwrap_or_else(FixedU128::one);
+				market.exchange_rate = market.exchange_rate.saturating_mul(ratio);
+			}
+			market.total_deposits = remaining;
+			Self::deposit_event(Event::LossSocialized(pool_id, asset_id, socialized, market.exchange_rate));
+		}
+		<Markets<T>>::insert(pool_id, asset_id, market);
+		Self::close_loan(pool_id, loan_id, &loan);
+		Self::deposit_event(Event::BadDebtWrittenOff(pool_id, loan_id, asset_id, loan.principal, loan.interest, covered, socialized));
+		Ok(())
+	}
+
+	/// Whether `who` has any deposit or pledged item left in a pool.
+	fn has_collateral(pool_id: PoolId, who: &T::AccountId) -> bool {
+		<Deposits<T>>::iter_prefix((pool_id, who.clone())).any(|(asset_id, _)| !Self::deposit_balance(pool_id, who, asset_id).is_zero())
+			|| <ItemCollateral<T>>::iter_prefix((pool_id, who.clone())).next().is_some()
+	}
+
+	/// Apply the proceeds a bidder paid in the loan's asset: repay what is still owed, deposit
+	/// any surplus for the item's previous owner, and write off debt the owner can no longer
+	/// cover. Returns the amounts repaid and deposited.
+	fn settle_auction(pool_id: PoolId, auction: &AuctionOf<T>, bidder: &T::AccountId, proceeds: T::Balance) -> Result<(T::Balance, T::Balance), DispatchError> {
+		let repaid = proceeds.min(<Self as Lending>::amount_owed(pool_id, auction.loan_id));
+		if !repaid.is_zero() {
+			Self::do_repay(pool_id, auction.loan_id, bidder, Some(repaid))?;
+		}
+		let surplus = proceeds.saturating_sub(repaid);
+		if !surplus.is_zero() {
+			<Self as Lending>::deposit(pool_id, auction.asset_id, auction.owner.clone(), surplus)?;
+		}
+		if <Loans<T>>::contains_key(pool_id, auction.loan_id) && !Self::has_collateral(pool_id, &auction.owner) {
+			Self::write_off(pool_id, auction.loan_id)?;
+		}
+		Ok((repaid, surplus))
+	}
+
+	/// Bring a market's reward indexes up to the current block, spreading the emissions since the
+	/// last update over what was supplied and borrowed in between. Must run before either total
+	/// changes. Emissions while nothing is supplied or borrowed are not paid to anyone.
+	fn update_reward_index(pool_id: PoolId, asset_id: T::AssetId) -> RewardIndex<T::Moment> {
+		let now = Self::now();
+		let mut index = Self::reward_indexes(pool_id, asset_id);
+		if index.updated_at == now {
+			return index;
+		}
+		let elapsed: T::Balance = now.saturating_sub(index.updated_at).into();
+		if let Some(market) = Self::markets(pool_id, asset_id) {
+			let speed = Self::reward_speeds(pool_id, asset_id);
+			let growth = |speed: T::Balance, total: T::Balance| FixedU128::checked_from_rational(speed.saturating_mul(elapsed), total).unwrap_or_else(Zero::zero);
+			index.supply = index.supply.saturating_add(growth(speed.supply, market.total_deposits));
+			index.borrow = index.borrow.saturating_add(growth(speed.borrow, market.total_borrows));
+		}
+		index.updated_at = now;
+		<RewardIndexes<T>>::insert(pool_id, asset_id, index);
+		index
+	}
+
+	/// Credit `who` with the rewards earned on their deposit in a market since it last changed.
+	fn accrue_supply_rewards(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId) {
+		let index = Self::update_reward_index(pool_id, asset_id);
+		let growth = index.supply.saturating_sub(Self::supplier_reward_indexes((pool_id, who.clone()), asset_id));
+		Self::credit_rewards(pool_id, who, growth.saturating_mul_int(Self::deposit_balance(pool_id, who, asset_id)));
+		<SupplierRewardIndexes<T>>::insert((pool_id, who.clone()), asset_id, index.supply);
+	}
+
+	/// Credit `who` with the rewards earned on their borrowed principal in a market since it last
+	/// changed. Must run before every change to the principal of the account's loans.
+	fn accrue_borrow_rewards(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId) {
+		let index = Self::update_reward_index(pool_id, asset_id);
+		let growth = index.borrow.saturating_sub(Self::borrower_reward_indexes((pool_id, who.clone()), asset_id));
+		Self::credit_rewards(pool_id, who, growth.saturating_mul_int(Self::borrowed_principal(pool_id, who, asset_id)));
+		<BorrowerRewardIndexes<T>>::insert((pool_id, who.clone()), asset_id, index.borrow);
+	}
+
+	fn credit_rewards(pool_id: PoolId, who: &T::AccountId, amount: T::Balance) {
+		if !amount.is_zero() {
+			<AccruedRewards<T>>::mutate(pool_id, who, |accrued| *accrued = accrued.saturating_add(amount));
+		}
+	}
+
+	/// Outstanding principal of `who`'s own loans of an asset in a pool.
+	fn borrowed_principal(pool_id: PoolId, who: &T::AccountId, asset_id: T::AssetId) -> T::Balance {
+		<LoansByBorrower<T>>::iter_prefix((pool_id, who.clone()))
+			.filter_map(|(loan_id, _)| Self::loans(pool_id, loan_id))
+			.filter(|loan| loan.asset_id == asset_id)
+			.fold(Zero::zero(), |total: T::Balance, loan| total.saturating_add(loan.principal))
+	}
+
+	fn value_of(asset_id: T::AssetId, amount: T::Balance) -> Result<T::Balance, DispatchError> {
+		let price = T::PriceFeed::get_price(&asset_id).ok_or(Error::<T>::PriceUnavailable)?;
+		Ok(price.saturating_mul_int(amount))
+	}
+
+	/// Amount of `asset_id` worth `value` at current prices.
+	fn amount_of(asset_id: T::AssetId, value: T::Balance) -> Result<T::Balance, DispatchError> {
+		let price = T::PriceFeed::get_price(&asset_id).ok_or(Error::<T>::PriceUnavailable)?;
+		Ok(price.reciprocal().ok_or(Error::<T>::PriceUnavailable)?.saturating_mul_int(value))
+	}
+
+	/// Value an account's collateral and debt in a single pool. Deposits and loans in other pools
+	/// are never considered.
+	pub fn account_position(pool_id: PoolId, who: &T::AccountId) -> Result<AccountPosition<T::Balance>, DispatchError> {
+		let mut position = AccountPosition::<T::Balance>::default();
+		for (asset_id, stored) in <Deposits<T>>::iter_prefix((pool_id, who.clone())) {
+			let market = match Self::markets(pool_id, asset_id) {
+				Some(market) => market,
+				None => continue,
+			};
+			let amount = Self::written_down(pool_id, who, asset_id, stored, &market);
+			let (collateral_factor, liquidation_threshold) = Self::risk_parameters(pool_id, who, asset_id, &market);
+			let value = Self::value_of(asset_id, amount)?;
+			position.borrowing_power = position.borrowing_power.saturating_add(collateral_factor * value);
+			position.liquidation_collateral = position.liquidation_collateral.saturating_add(liquidation_threshold * value);
+		}
+		// Items at auction are already being sold off and no longer back anything.
+		for (item_id, ()) in <ItemCollateral<T>>::iter_prefix((pool_id, who.clone())) {
+			if <Auctions<T>>::contains_key(pool_id, &item_id) {
+				continue;
+			}
+			if let Some(appraisal) = Self::item_appraisals(pool_id, &item_id) {
+				position.borrowing_power = position.borrowing_power.saturating_add(appraisal.collateral_factor * appraisal.value);
+				position.liquidation_collateral = position.liquidation_collateral.saturating_add(appraisal.liquidation_threshold * appraisal.value);
+			}
+		}
+		// Debt counts against whoever backs it: own loans plus loans taken out by delegatees.
+		let own_loans = <LoansByBorrower<T>>::iter_prefix((pool_id, who.clone())).map(|(loan_id, _)| loan_id);
+		let delegated_loans = <DelegatedLoans<T>>::iter_prefix((pool_id, who.clone())).map(|(loan_id, _)| loan_id);
+		for loan_id in own_loans.chain(delegated_loans) {
+			if let Some(mut loan) = Self::loans(pool_id, loan_id) {
+				if loan.backer() != who {
+					continue;
+				}
+				Self::accrue(pool_id, &mut loan);
+				let value = Self::value_of(loan.asset_id, loan.principal.saturating_add(loan.interest))?;
+				position.debt = position.debt.saturating_add(value);
+			}
+		}
+		Ok(position)
+	}
+
+	/// Borrowing headroom or shortfall of an account in a pool.
+	pub fn account_liquidity(pool_id: PoolId, who: &T::AccountId) -> Result<AccountLiquidity<T::Balance>, DispatchError> {
+		let position = Self::account_position(pool_id, who)?;
+		Ok(AccountLiquidity {
+			liquidity: position.borrowing_power.saturating_sub(position.debt),
+			shortfall: position.debt.saturating_sub(position.borrowing_power),
+		})
+	}
+
+	/// Liquidation collateral divided by debt. The account can be liquidated once this drops below
+	/// one; an account without debt reports the maximum value.
+	pub fn health_factor(pool_id: PoolId, who: &T::AccountId) -> Result<FixedU128, DispatchError> {
+		let position = Self::account_position(pool_id, who)?;
+		if position.debt.is_zero() {
+			return Ok(FixedU128::max_value());
+		}
+		Ok(FixedU128::checked_from_rational(position.liquidation_collateral, position.debt).unwrap_or_else(FixedU128::max_value))
+	}
+
+	fn has_loans(pool_id: PoolId, who: &T::AccountId) -> bool {
+		<LoansByBorrower<T>>::iter_prefix((pool_id, who.clone())).next().is_some()
+			|| <DelegatedLoans<T>>::iter_prefix((pool_id, who.clone())).next().is_some()
+	}
+
+	/// Check that the market can lend `amount` and that `backer`'s collateral covers it.
+	fn ensure_can_borrow(pool_id: PoolId, backer: &T::AccountId, asset_id: T::AssetId, amount: T::Balance) -> DispatchResult {
+		ensure!(Self::market(pool_id, asset_id)?.available_liquidity() >= amount, Error::<T>::InsufficientLiquidity);
+		if let Some(category_id) = Self::account_emode(pool_id, backer) {
+			ensure!(Self::asset_emode_category(pool_id, asset_id) == Some(category_id), Error::<T>::AssetNotInEModeCategory);
+		}
+		let position = Self::account_position(pool_id, backer)?;
+		let amount_value = Self::value_of(asset_id, amount)?;
+		ensure!(position.borrowing_power >= position.debt.saturating_add(amount_value), Error::<T>::InsufficientCollateral);
+		Ok(())
+	}
+
+	fn new_loan(asset_id: T::AssetId, borrower: &T::AccountId, delegator: Option<&T::AccountId>, principal: T::Balance, duration: T::Moment, rate_mode: RateMode, stable_rate: Permill) -> LoanOf<T> {
+		let now = Self::now();
+		Loan {
+			borrower: borrower.clone(),
+			asset_id,
+			principal,
+			interest: Zero::zero(),
+			start: now,
+			duration,
+			accrued_at: now,
+			delegator: delegator.cloned(),
+			rate_mode,
+			stable_rate,
+		}
+	}
+
+	/// Record a new loan under the next free id and index it by borrower, delegator and asset.
+	/// Collateral and liquidity checks are the caller's responsibility.
+	fn open_loan(pool_id: PoolId, asset_id: T::AssetId, borrower: &T::AccountId, delegator: Option<&T::AccountId>, amount: T::Balance, duration: T::Moment, rate_mode: RateMode) -> Result<LoanId, DispatchError> {
+		let loan_id = Self::next_loan_id(pool_id);
+		let next_id = loan_id.checked_add(1).ok_or(Error::<T>::LoanIdOverflow)?;
+		NextLoanId::<T>::insert(pool_id, next_id);
+		Self::accrue_borrow_rewards(pool_id, borrower, asset_id);
+		<Markets<T>>::mutate(pool_id, asset_id, |market| {
+			if let Some(market) = market {
+				market.total_borrows = market.total_borrows.saturating_add(amount);
+			}
+		});
+		let stable_rate = match rate_mode {
+			RateMode::Stable => Self::borrow_rate(pool_id, asset_id),
+			RateMode::Variable => Permill::zero(),
+		};
+		<Loans<T>>::insert(pool_id, loan_id, Self::new_loan(asset_id, borrower, delegator, amount, duration, rate_mode, stable_rate));
+		<LoansByBorrower<T>>::insert((pool_id, borrower.clone()), loan_id, ());
+		if let Some(delegator) = delegator {
+			<DelegatedLoans<T>>::insert((pool_id, delegator.clone()), loan_id, ());
+		}
+		<LoansByAsset<T>>::insert((pool_id, asset_id), loan_id, ());
+		Ok(loan_id)
+	}
+
+	fn ensure_borrower(pool_id: PoolId, loan_id: LoanId, who: &T::AccountId) -> DispatchResult {
+		let loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+		ensure!(&loan.borrower == who, Error::<T>::NotLoanBorrower);
+		Ok(())
+	}
+
+	/// Bring a loan's accrued interest up to the current block.
+	fn accrue(pool_id: PoolId, loan: &mut LoanOf<T>) {
+		let now = Self::now();
+		let elapsed = now.saturating_sub(loan.accrued_at);
+		let interest = match loan.rate_mode {
+			RateMode::Variable => <Self as Lending>::calculate_interest(pool_id, loan.asset_id, loan.principal, elapsed),
+			RateMode::Stable => loan.stable_rate.mul_floor(loan.principal.saturating_mul(elapsed.into())),
+		};
+		loan.interest = loan.interest.saturating_add(interest);
+		loan.accrued_at = now;
+	}
+
+	/// Apply a repayment from `payer` to a loan. Interest is settled before principal and `None`
+	/// repays everything owed. Paid interest is shared between depositors and reserves.
+	fn do_repay(pool_id: PoolId, loan_id: LoanId, payer: &T::AccountId, amount: Option<T::Balance>) -> Result<(T::Balance, T::Balance), DispatchError> {
+		let mut loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+		Self::accrue(pool_id, &mut loan);
+
+		let owed = loan.principal.saturating_add(loan.interest);
+		let paid = amount.map_or(owed, |amount| amount.min(owed));
+		let interest_paid = paid.min(loan.interest);
+		let principal_paid = paid.saturating_sub(interest_paid);
+		loan.interest = loan.interest.saturating_sub(interest_paid);
+		loan.principal = loan.principal.saturating_sub(principal_paid);
+
+		Self::accrue_borrow_rewards(pool_id, &loan.borrower, loan.asset_id);
+		<Markets<T>>::mutate(pool_id, loan.asset_id, |market| {
+			if let Some(market) = market {
+				market.total_borrows = market.total_borrows.saturating_sub(principal_paid);
+			}
+		});
+		Self::distribute_interest(pool_id, loan.asset_id, interest_paid);
+		Self::deposit_event(Event::Repaid(pool_id, loan_id, loan.borrower.clone(), payer.clone(), interest_paid, principal_paid));
+
+		if loan.principal.is_zero() && loan.interest.is_zero() {
+			Self::close_loan(pool_id, loan_id, &loan);
+		} else {
+			<Loans<T>>::insert(pool_id, loan_id, loan);
+		}
+		Ok((interest_paid, principal_paid))
+	}
+
+	/// A loan can be liquidated once its borrower's health factor in the pool is below one or
+	/// the loan is past maturity.
+	pub fn is_liquidatable(pool_id: PoolId, loan: &LoanOf<T>) -> bool {
+		if Self::now() > loan.maturity() {
+			return true;
+		}
+		Self::health_factor(pool_id, loan.backer()).map_or(false, |health| health < FixedU128::one())
+	}
+
+	/// Up to `max` liquidatable loans across all pools.
+	pub fn liquidatable_loans(max: usize) -> Vec<(PoolId, LoanId)> {
+		<Loans<T>>::iter()
+			.filter(|(pool_id, _, loan)| Self::is_liquidatable(*pool_id, loan))
+			.map(|(pool_id, loan_id, _)| (pool_id, loan_id))
+			.take(max)
+			.collect()
+	}
+
+	/// Take up to `value` worth of `who`'s deposits in a pool and hand each seized amount to
+	/// `seized`. Returns the value actually taken, which falls short if collateral runs out.
+	fn seize(pool_id: PoolId, who: &T::AccountId, value: T::Balance, mut seized: impl FnMut(T::AssetId, T::Balance)) -> Result<T::Balance, DispatchError> {
+		let mut remaining = value;
+		let asset_ids: Vec<_> = <Deposits<T>>::iter_prefix((pool_id, who.clone())).map(|(asset_id, _)| asset_id).collect();
+		for asset_id in asset_ids {
+			if remaining.is_zero() {
+				break;
+			}
+			Self::touch_deposit(pool_id, who, asset_id);
+			let amount = Self::deposits((pool_id, who.clone()), asset_id);
+			let price = T::PriceFeed::get_price(&asset_id).ok_or(Error::<T>::PriceUnavailable)?;
+			let deposit_value = price.saturating_mul_int(amount);
+			let (taken_value, taken) = if deposit_value <= remaining {
+				(deposit_value, amount)
+			} else {
+				let taken = price.reciprocal().ok_or(Error::<T>::PriceUnavailable)?.saturating_mul_int(remaining).min(amount);
+				(remaining, taken)
+			};
+			<Deposits<T>>::insert((pool_id, who.clone()), asset_id, amount.saturating_sub(taken));
+			seized(asset_id, taken);
+			remaining = remaining.saturating_sub(taken_value);
+		}
+		Ok(value.saturating_sub(remaining))
+	}
+
+	/// Repay `loan_id` out of the borrower's collateral. A liquidator pays the debt in the loan's
+	/// asset into the `PoolAccount` and is credited the seized collateral in the same pool; without one the collateral is sold to repay the
+	/// loan and the penalty is added to reserves. Debt left once the collateral runs out is
+	/// written off.
+	fn do_liquidate(pool_id: PoolId, loan_id: LoanId, liquidator: Option<&T::AccountId>) -> DispatchResult {
+		let loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+		ensure!(Self::is_liquidatable(pool_id, &loan), Error::<T>::NotLiquidatable);
+		let owed = <Self as Lending>::amount_owed(pool_id, loan_id);
+		let owed_value = Self::value_of(loan.asset_id, owed)?;
+		let penalty_value = T::LiquidationPenalty::get() * owed_value;
+
+		let credit_liquidator = |who: &T::AccountId| move |asset_id: T::AssetId, amount: T::Balance| {
+			Self::touch_deposit(pool_id, who, asset_id);
+			<Deposits<T>>::mutate((pool_id, who.clone()), asset_id, |balance| *balance = balance.saturating_add(amount));
+		};
+		let sell = |asset_id: T::AssetId, amount: T::Balance| {
+			<Markets<T>>::mutate(pool_id, asset_id, |market| {
+				if let Some(market) = market {
+					market.total_deposits = market.total_deposits.saturating_sub(amount);
+				}
+			});
+		};
+		let backer = loan.backer();
+		let (covered_value, penalty_taken) = match liquidator {
+			Some(who) => (
+				Self::seize(pool_id, backer, owed_value, credit_liquidator(who))?,
+				Self::seize(pool_id, backer, penalty_value, credit_liquidator(who))?,
+			),
+			None => (
+				Self::seize(pool_id, backer, owed_value, sell)?,
+				Self::seize(pool_id, backer, penalty_value, |asset_id, amount| {
+					sell(asset_id, amount);
+					Self::add_reserves(pool_id, asset_id, amount);
+				})?,
+			),
+		};
+
+		let repaid = if covered_value.is_zero() {
+			Zero::zero()
+		} else if covered_value == owed_value {
+			owed
+		} else {
+			let price = T::PriceFeed::get_price(&loan.asset_id).ok_or(Error::<T>::PriceUnavailable)?;
+			price.reciprocal().ok_or(Error::<T>::PriceUnavailable)?.saturating_mul_int(covered_value).min(owed)
+		};
+		if !repaid.is_zero() {
+			if let Some(who) = liquidator {
+				T::Assets::transfer(loan.asset_id, who.clone(), T::PoolAccount::get(), repaid)?;
+			}
+			let payer = liquidator.cloned().unwrap_or_else(|| loan.borrower.clone());
+			Self::do_repay(pool_id, loan_id, &payer, Some(repaid))?;
+		}
+		let bad_debt = <Loans<T>>::contains_key(pool_id, loan_id) && !Self::has_collateral(pool_id, backer);
+		ensure!(!repaid.is_zero() || bad_debt, Error::<T>::InsufficientCollateral);
+		Self::deposit_event(Event::Liquidated(pool_id, loan_id, liquidator.cloned(), repaid, covered_value.saturating_add(penalty_taken)));
+		if bad_debt {
+			Self::write_off(pool_id, loan_id)?;
+		}
+		Ok(())
+	}
+
+	fn remove_instruction(instruction_id: InstructionId, owner: &T::AccountId) {
+		<Instructions<T>>::remove(instruction_id);
+		<InstructionCount<T>>::mutate(owner, |count| *count = count.saturating_sub(1));
+	}
+
+	/// Amount of `to_asset` worth `amount` of `from_asset` at current prices.
+	fn convert(from_asset: T::AssetId, to_asset: T::AssetId, amount: T::Balance) -> Result<T::Balance, DispatchError> {
+		let from_price = T::PriceFeed::get_price(&from_asset).ok_or(Error::<T>::PriceUnavailable)?;
+		let to_price = T::PriceFeed::get_price(&to_asset).ok_or(Error::<T>::PriceUnavailable)?;
+		let rate = from_price.checked_div(&to_price).ok_or(Error::<T>::PriceUnavailable)?;
+		Ok(rate.saturating_mul_int(amount))
+	}
+
+	/// Number of deposits, pledged items and loans `who` holds in a pool.
+	fn position_count(pool_id: PoolId, who: &T::AccountId) -> u32 {
+		let key = (pool_id, who.clone());
+		let count = <Deposits<T>>::iter_prefix(key.clone()).count()
+			+ <ItemCollateral<T>>::iter_prefix(key.clone()).count()
+			+ <LoansByBorrower<T>>::iter_prefix(key.clone()).count()
+			+ <DelegatedLoans<T>>::iter_prefix(key).count();
+		count.min(u32::MAX as usize) as u32
+	}
+
+	/// Weight of checking and carrying out a standing instruction for an account holding
+	/// `positions` positions in the pool. Valuing the account reads each position together with
+	/// its market and risk parameters, and repaying walks the borrower's loans once more.
+	fn instruction_weight(positions: u32) -> Weight {
+		T::DbWeight::get().reads_writes(8 + 6 * positions as u64, 8)
+	}
+
+	/// Check one standing instruction and carry it out if its condition holds. Instructions whose
+	/// loan is gone are dropped; ones that fail to execute are left for a later attempt.
+	fn process_instruction(instruction_id: InstructionId) {
+		let standing = match Self::instructions(instruction_id) {
+			Some(standing) => standing,
+			None => return,
+		};
+		let loan_id = standing.instruction.loan_id();
+		if !<Loans<T>>::contains_key(standing.pool_id, loan_id) {
+			Self::remove_instruction(instruction_id, &standing.owner);
+			Self::deposit_event(Event::InstructionRemoved(instruction_id));
+			return;
+		}
+		let executed = with_transaction(|| match Self::execute_instruction(&standing) {
+			Ok(Some(amounts)) => TransactionOutcome::Commit(Ok(Some(amounts))),
+			Ok(None) => TransactionOutcome::Rollback(Ok(None)),
+			Err(e) => TransactionOutcome::Rollback(Err(e)),
+		});
+		if let Ok(Some((sold, repaid))) = executed {
+			Self::remove_instruction(instruction_id, &standing.owner);
+			Self::deposit_event(Event::InstructionExecuted(instruction_id, sold, repaid));
+		}
+	}
+
+	/// Returns the collateral sold and the amount repaid, or `None` if the condition is not met
+	/// or the owner cannot yet pay.
+	fn execute_instruction(standing: &StandingInstructionOf<T>) -> Result<Option<(T::Balance, T::Balance)>, DispatchError> {
+		let pool_id = standing.pool_id;
+		match standing.instruction {
+			Instruction::RepayAtMaturity { loan_id } => {
+				let loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+				if Self::now() < loan.maturity() {
+					return Ok(None);
+				}
+				let owed = <Self as Lending>::amount_owed(pool_id, loan_id);
+				if T::Assets::balance(loan.asset_id, &standing.owner) < owed {
+					return Ok(None);
+				}
+				T::Assets::transfer(loan.asset_id, standing.owner.clone(), T::PoolAccount::get(), owed)?;
+				let (interest_paid, principal_paid) = Self::do_repay(pool_id, loan_id, &standing.owner, None)?;
+				Ok(Some((Zero::zero(), interest_paid.saturating_add(principal_paid))))
+			}
+			Instruction::StopLoss { threshold, collateral_asset, loan_id } => {
+				if Self::health_factor(pool_id, &standing.owner)? >= threshold {
+					return Ok(None);
+				}
+				let loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+				let owed = <Self as Lending>::amount_owed(pool_id, loan_id);
+				let mut market = Self::market(pool_id, collateral_asset)?;
+				let deposited = Self::deposit_balance(pool_id, &standing.owner, collateral_asset);
+				// Sell only what is needed, and no more than is deposited or can leave the market.
+				let sold = Self::convert(loan.asset_id, collateral_asset, owed)?.min(deposited).min(market.available_liquidity());
+				ensure!(!sold.is_zero(), Error::<T>::InsufficientBalance);
+				let repaid = Self::convert(collateral_asset, loan.asset_id, sold)?.min(owed);
+
+				Self::accrue_yield(pool_id, &standing.owner, collateral_asset, &market);
+				market.total_deposits = market.total_deposits.saturating_sub(sold);
+				<Markets<T>>::insert(pool_id, collateral_asset, market);
+				<Deposits<T>>::insert((pool_id, standing.owner.clone()), collateral_asset, deposited.saturating_sub(sold));
+				Self::do_repay(pool_id, loan_id, &standing.owner, Some(repaid))?;
+				Ok(Some((sold, repaid)))
+			}
+		}
+	}
+
+	fn close_loan(pool_id: PoolId, loan_id: LoanId, loan: &LoanOf<T>) {
+		<Loans<T>>::remove(pool_id, loan_id);
+		<LoansByBorrower<T>>::remove((pool_id, loan.borrower.clone()), loan_id);
+		if let Some(delegator) = &loan.delegator {
+			<DelegatedLoans<T>>::remove((pool_id, delegator.clone()), loan_id);
+		}
+		<LoansByAsset<T>>::remove((pool_id, loan.asset_id), loan_id);
+		Self::deposit_event(Event::LoanClosed(pool_id, loan_id, loan.borrower.clone()));
+	}
+
+	fn do_extend_loan(pool_id: PoolId, loan_id: LoanId, extra_duration: T::Moment) -> DispatchResult {
+		ensure!(!extra_duration.is_zero(), Error::<T>::ZeroExtension);
+		let mut loan = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+		Self::accrue(pool_id, &mut loan);
+
+		// The borrower pays the accrued interest and the fee up front, and both are shared out
+		// like repaid interest.
+		let fee = T::ExtensionFee::get().mul_floor(loan.principal);
+		let charged = loan.interest.saturating_add(fee);
+		T::Assets::transfer(loan.asset_id, loan.borrower.clone(), T::PoolAccount::get(), charged)?;
+		loan.interest = Zero::zero();
+		loan.duration = loan.duration.saturating_add(extra_duration);
+		Self::distribute_interest(pool_id, loan.asset_id, charged);
+
+		Self::deposit_event(Event::LoanExtended(pool_id, loan_id, extra_duration, fee, loan.maturity()));
+		<Loans<T>>::insert(pool_id, loan_id, loan);
+		Ok(())
+	}
+
+	/// Open a loan on `new_offer` terms and use it to repay `loan_id` in full. Callers must run
+	/// this inside a transaction so a failed repayment also discards the new loan.
+	fn do_refinance(pool_id: PoolId, loan_id: LoanId, new_offer: LoanTerms<T::Balance, T::Moment>) -> Result<LoanId, DispatchError> {
+		let old = Self::loans(pool_id, loan_id).ok_or(Error::<T>::LoanNotFound)?;
+		let owed = <Self as Lending>::amount_owed(pool_id, loan_id);
+		ensure!(new_offer.amount >= owed, Error::<T>::InsufficientRefinanceAmount);
+
+		// Only the excess over the old debt is new exposure for the pool, and for a delegated
+		// loan it is drawn from the remaining allowance.
+		let extra = new_offer.amount.saturating_sub(owed);
+		if !extra.is_zero() {
+			if let Some(delegator) = &old.delegator {
+				<Delegations<T>>::try_mutate((pool_id, delegator.clone()), (old.borrower.clone(), old.asset_id), |allowance| -> DispatchResult {
+					*allowance = allowance.checked_sub(&extra).ok_or(Error::<T>::InsufficientDelegation)?;
+					Ok(())
+				})?;
+			}
+			Self::ensure_can_borrow(pool_id, old.backer(), old.asset_id, extra)?;
+		}
+
+		let new_loan_id = Self::open_loan(pool_id, old.asset_id, &old.borrower, old.delegator.as_ref(), new_offer.amount, new_offer.duration, old.rate_mode)?;
+		Self::deposit_event(Event::Borrowed(pool_id, new_loan_id, old.asset_id, old.borrower.clone(), new_offer.amount, new_offer.duration));
+		let (interest_paid, principal_paid) = Self::do_repay(pool_id, loan_id, &old.borrower, None)?;
+		Self::deposit_event(Event::LoanRefinanced(pool_id, loan_id, new_loan_id, interest_paid, principal_paid, new_offer.amount));
+		Ok(new_loan_id)
+	}
+}
+
+impl<T: Config> Lending for Pallet<T> {
+	type AssetId = T::AssetId;
+	type AccountId = T::AccountId;
+	type Balance = T::Balance;
+	type Moment = T::Moment;
+
+	fn deposit(pool_id: PoolId, asset_id: Self::AssetId, from: Self::AccountId, amount: Self::Balance) -> DispatchResult {
+		let mut market = Self::market(pool_id, asset_id)?;
+		Self::accrue_yield(pool_id, &from, asset_id, &market);
+		market.total_deposits = market.total_deposits.saturating_add(amount);
+		<Markets<T>>::insert(pool_id, asset_id, market);
+		<Deposits<T>>::mutate((pool_id, from), asset_id, |balance| *balance = balance.saturating_add(amount));
+		Ok(())
+	}
+
+	fn withdraw(pool_id: PoolId, asset_id: Self::AssetId, to: Self::AccountId, amount: Self::Balance) -> DispatchResult {
+		let mut market = Self::market(pool_id, asset_id)?;
+		let current_balance = Self::written_down(pool_id, &to, asset_id, Self::deposits((pool_id, to.clone()), asset_id), &market);
+		let new_balance = current_balance.checked_sub(&amount).ok_or(Error::<T>::InsufficientBalance)?;
+		ensure!(market.available_liquidity() >= amount, Error::<T>::InsufficientLiquidity);
+		if Self::has_loans(pool_id, &to) {
+			let position = Self::account_position(pool_id, &to)?;
+			let (collateral_factor, _) = Self::risk_parameters(pool_id, &to, asset_id, &market);
+			// Round the lost power up so a withdrawal never leaves the debt uncovered by a fraction.
+			let lost_power = collateral_factor.mul_ceil(Self::value_of(asset_id, amount)?);
+			ensure!(position.borrowing_power.saturating_sub(lost_power) >= position.debt, Error::<T>::InsufficientCollateral);
+		}
+		Self::accrue_yield(pool_id, &to, asset_id, &market);
+		market.total_deposits = market.total_deposits.saturating_sub(amount);
+		<Markets<T>>::insert(pool_id, asset_id, market);
+		<Deposits<T>>::insert((pool_id, to), asset_id, new_balance);
+		Ok(())
+	}
+
+	fn borrow(pool_id: PoolId, asset_id: Self::AssetId, borrower: Self::AccountId, amount: Self::Balance, duration: Self::Moment, rate_mode: RateMode) -> Result<LoanId, DispatchError> {
+		Self::ensure_can_borrow(pool_id, &borrower, asset_id, amount)?;
+		Self::open_loan(pool_id, asset_id, &borrower, None, amount, duration, rate_mode)
+	}
+
+	fn repay(pool_id: PoolId, loan_id: LoanId, payer: Self::AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::do_repay(pool_id, loan_id, &payer, Some(amount)).map(|_| ())
+	}
+
+	fn repay_all(pool_id: PoolId, loan_id: LoanId, payer: Self::AccountId) -> DispatchResult {
+		Self::do_repay(pool_id, loan_id, &payer, None).map(|_| ())
+	}
+
+	/// Principal plus interest accrued up to now, or zero if the loan does not exist.
+	fn amount_owed(pool_id: PoolId, loan_id: LoanId) -> Self::Balance {
+		Self::loans(pool_id, loan_id)
+			.map(|mut loan| {
+				Self::accrue(pool_id, &mut loan);
+				loan.principal.saturating_add(loan.interest)
+			})
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn loans_of(pool_id: PoolId, borrower: Self::AccountId) -> Vec<LoanId> {
+		<LoansByBorrower<T>>::iter_prefix((pool_id, borrower)).map(|(loan_id, _)| loan_id).collect()
+	}
+
+	fn extend_loan(pool_id: PoolId, loan_id: LoanId, extra_duration: Self::Moment) -> DispatchResult {
+		Self::do_extend_loan(pool_id, loan_id, extra_duration)
+	}
+
+	fn refinance(pool_id: PoolId, loan_id: LoanId, new_offer: LoanTerms<Self::Balance, Self::Moment>) -> Result<LoanId, DispatchError> {
+		with_transaction(|| match Self::do_refinance(pool_id, loan_id, new_offer) {
+			Ok(new_loan_id) => TransactionOutcome::Commit(Ok(new_loan_id)),
+			Err(e) => TransactionOutcome::Rollback(Err(e)),
+		})
+	}
+
+	/// Interest on `amount` over `duration` blocks at the market's current borrow rate.
+	fn calculate_interest(pool_id: PoolId, asset_id: Self::AssetId, amount: Self::Balance, duration: Self::Moment) -> Self::Balance {
+		Self::borrow_rate(pool_id, asset_id).mul_floor(amount.saturating_mul(duration.into()))
+	}
 }
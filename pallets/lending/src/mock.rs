@@ -0,0 +1,151 @@
+use crate as pallet_lending;
+use crate::{ItemCustody, PriceFeed};
+use frame_support::{
+	derive_impl,
+	dispatch::DispatchResult,
+	ensure, parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::TestXt,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DispatchError, FixedPointNumber, FixedU128, Percent, Permill,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Assets: pallet_assets,
+		LendingModule: pallet_lending,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = u32;
+	type Balance = u64;
+}
+
+parameter_types! {
+	pub const ExtensionFee: Percent = Percent::from_percent(2);
+	pub const LiquidationPenalty: Permill = Permill::from_percent(10);
+	pub const RebalanceThreshold: Permill = Permill::from_percent(2);
+	pub const InsuranceFactor: Permill = Permill::zero();
+	pub const AuctionStartPremium: Permill = Permill::from_percent(20);
+	pub const AuctionPriceDecay: Permill = Permill::from_percent(10);
+	pub const PoolAccount: u64 = 998;
+}
+
+impl pallet_lending::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = u32;
+	type Balance = u64;
+	type Moment = u64;
+	type ExtensionFee = ExtensionFee;
+	type PriceFeed = TestPriceFeed;
+	type MarketAdminOrigin = frame_system::EnsureRoot<u64>;
+	type MaxMarketsPerPool = ConstU32<4>;
+	type MaxInstructionsPerBlock = ConstU32<2>;
+	type MaxInstructionsPerAccount = ConstU32<2>;
+	type LiquidationPenalty = LiquidationPenalty;
+	type MaxLiquidationsPerTx = ConstU32<2>;
+	type UnsignedInterval = ConstU64<5>;
+	type UnsignedPriority = ConstU64<100>;
+	type RebalanceThreshold = RebalanceThreshold;
+	type Assets = Assets;
+	type RewardAssetId = ConstU32<100>;
+	type RewardAccount = ConstU64<1_000>;
+	type InsuranceFactor = InsuranceFactor;
+	type ItemId = u32;
+	type Items = TestItems;
+	type ItemEscrowAccount = ConstU64<999>;
+	type PoolAccount = PoolAccount;
+	type AuctionStartPremium = AuctionStartPremium;
+	type AuctionPriceDecay = AuctionPriceDecay;
+	type GovernanceOrigin = frame_system::EnsureRoot<u64>;
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+/// Prices every asset at one unit, except asset 9 which has no price.
+pub struct TestPriceFeed;
+impl PriceFeed<u32> for TestPriceFeed {
+	fn get_price(asset_id: &u32) -> Option<FixedU128> {
+		if *asset_id == 9 {
+			None
+		} else {
+			Some(FixedU128::one())
+		}
+	}
+}
+
+thread_local! {
+	static ITEM_OWNERS: RefCell<BTreeMap<u32, u64>> = RefCell::new(BTreeMap::new());
+}
+
+/// Keeps item ownership in memory so tests can hand items out directly.
+pub struct TestItems;
+impl TestItems {
+	pub fn give(item_id: u32, owner: u64) {
+		ITEM_OWNERS.with(|owners| owners.borrow_mut().insert(item_id, owner));
+	}
+}
+impl ItemCustody<u64, u32> for TestItems {
+	fn owner(item_id: &u32) -> Option<u64> {
+		ITEM_OWNERS.with(|owners| owners.borrow().get(item_id).copied())
+	}
+
+	fn transfer(item_id: &u32, from: &u64, to: &u64) -> DispatchResult {
+		ensure!(Self::owner(item_id).as_ref() == Some(from), DispatchError::Other("not the item owner"));
+		TestItems::give(*item_id, *to);
+		Ok(())
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	ITEM_OWNERS.with(|owners| owners.borrow_mut().clear());
+	let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -1,66 +1,782 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate as pallet_lending;
-    use frame_support::{assert_ok, impl_outer_origin, parameter_types};
-    use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
-    use sp_core::H256;
-
-    impl_outer_origin! {
-        pub enum Origin for Test {}
-    }
-
-    #[derive(Clone, Eq, PartialEq)]
-    pub struct Test;
-    parameter_types! {
-        pub const BlockHashCount: u64 = 250;
-    }
-    impl frame_system::Config for Test {
-        type BaseCallFilter = frame_support::traits::Everything;
-        type BlockWeights = ();
-        type BlockLength = ();
-        type DbWeight = ();
-        type Origin = Origin;
-        type Call = ();
-        type Index = u64;
-        type BlockNumber = u64;
-        type Hash = H256;
-        type Hashing = BlakeTwo256;
-        type AccountId = u64;
-        type Lookup = IdentityLookup<Self::AccountId>;
-        type Header = Header;
-        type Event = ();
-        type BlockHashCount = BlockHashCount;
-        type Version = ();
-        type PalletInfo = PalletInfo;
-        type AccountData = ();
-        type OnNewAccount = ();
-        type OnKilledAccount = ();
-        type SystemWeightInfo = ();
-    }
-
-    impl Config for Test {
-        type Event = ();
-    }
-
-    type LendingModule = Module<Test>;
-
-    #[test]
-    fn it_deposits_assets() {
-        new_test_ext().execute_with(|| {
-            // Test deposit of assets
-            assert_ok!(LendingModule::deposit(Origin::signed(1), 1, 100));
-            assert_eq!(LendingModule::deposits((1, 1)), 100);
-        });
-    }
-
-    #[test]
-    fn it_withdraws_assets() {
-        new_test_ext().execute_with(|| {
-            // Test withdrawal of assets
-            assert_ok!(LendingModule::deposit(Origin::signed(1), 1, 100));
-            assert_ok!(LendingModule::withdraw(Origin::signed(1), 1, 50));
-            assert_eq!(LendingModule::deposits((1, 1)), 50);
-        });
-    }
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::{OffchainWorker, OnIdle}, unsigned::ValidateUnsigned};
+use codec::Decode;
+use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+use sp_runtime::{traits::BadOrigin, transaction_validity::{InvalidTransaction, TransactionSource}, BuildStorage, FixedU128, Permill};
+
+const FLAT_FIVE_PERCENT: RateModel = RateModel { base_rate: Permill::from_percent(5), slope: Permill::zero() };
+/// Lets the repayment tests read all collected interest off `Reserves`.
+const ALL_TO_RESERVES: Permill = Permill::from_percent(100);
+
+/// Create a pool listing assets 1, 2 and 9 at 50% collateral factor and a flat 5% per block.
+fn create_pool() -> PoolId {
+	let pool_id = LendingModule::next_pool_id();
+	assert_ok!(LendingModule::create_pool(RuntimeOrigin::root()));
+	for asset_id in [1, 2, 9] {
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), pool_id, asset_id, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES));
+	}
+	pool_id
+}
+
+/// Pool 0 with liquidity from account 9 and 1_000 of asset 1 posted as collateral by account 1.
+fn new_funded_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(9), 0, 1, 1_000));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(9), 0, 2, 1_000));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 1_000));
+	});
+	ext
+}
+
+#[test]
+fn it_deposits_assets() {
+	new_test_ext().execute_with(|| {
+		// Test deposit of assets
+		create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_eq!(LendingModule::deposits((0, 1), 1), 100);
+		assert_eq!(LendingModule::markets(0, 1).unwrap().total_deposits, 100);
+	});
+}
+
+#[test]
+fn it_withdraws_assets() {
+	new_test_ext().execute_with(|| {
+		// Test withdrawal of assets
+		create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 100));
+		assert_ok!(LendingModule::withdraw(RuntimeOrigin::signed(1), 0, 1, 50));
+		assert_eq!(LendingModule::deposits((0, 1), 1), 50);
+		assert_eq!(LendingModule::markets(0, 1).unwrap().total_deposits, 50);
+	});
+}
+
+#[test]
+fn it_repays_interest_before_principal() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 100, 10, RateMode::Variable));
+		System::set_block_number(3);
+		// 5% per block over two blocks.
+		assert_eq!(<LendingModule as Lending>::amount_owed(0, 0), 110);
+		assert_ok!(LendingModule::repay(RuntimeOrigin::signed(1), 0, 0, 30));
+		let loan = LendingModule::loans(0, 0).unwrap();
+		assert_eq!((loan.principal, loan.interest), (80, 0));
+		assert_eq!(LendingModule::reserves(0, 1), 10);
+	});
+}
+
+#[test]
+fn it_caps_overpayment_and_closes_loan() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 100, 10, RateMode::Variable));
+		System::set_block_number(2);
+		assert_ok!(LendingModule::repay(RuntimeOrigin::signed(1), 0, 0, 1_000));
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert!(<LendingModule as Lending>::loans_of(0, 1).is_empty());
+		assert_eq!(LendingModule::reserves(0, 1), 5);
+		assert_noop!(LendingModule::repay(RuntimeOrigin::signed(1), 0, 0, 1), Error::<Test>::LoanNotFound);
+	});
+}
+
+#[test]
+fn it_repays_all_exactly() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 200, 10, RateMode::Variable));
+		System::set_block_number(4);
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(1), 0, 0));
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert_eq!(LendingModule::reserves(0, 1), 30);
+	});
+}
+
+#[test]
+fn it_repays_on_behalf_of_borrower() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 100, 10, RateMode::Variable));
+		assert_noop!(LendingModule::repay(RuntimeOrigin::signed(2), 0, 0, 40), Error::<Test>::NotLoanBorrower);
+		assert_ok!(LendingModule::repay_on_behalf(RuntimeOrigin::signed(2), 0, 0, 40));
+		assert_eq!(LendingModule::loans(0, 0).unwrap().principal, 60);
+		assert_noop!(LendingModule::repay_on_behalf(RuntimeOrigin::signed(2), 0, 1, 40), Error::<Test>::LoanNotFound);
+	});
+}
+
+#[test]
+fn it_keeps_concurrent_loans_separate() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 100, 10, RateMode::Variable));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 50, 20, RateMode::Variable));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 70, 5, RateMode::Variable));
+		assert_eq!(LendingModule::next_loan_id(0), 3);
+
+		let mut loans = <LendingModule as Lending>::loans_of(0, 1);
+		loans.sort();
+		assert_eq!(loans, vec![0, 1, 2]);
+		assert_eq!(LendingModule::loans(0, 1).unwrap().maturity(), 21);
+		assert!(<LoansByAsset<Test>>::contains_key((0, 2), 2));
+
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(1), 0, 0));
+		assert_eq!(LendingModule::loans(0, 1).unwrap().principal, 50);
+		assert_eq!(<LendingModule as Lending>::loans_of(0, 1).len(), 2);
+	});
+}
+
+#[test]
+fn it_extends_loan_charging_interest_and_fee() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 100, 10, RateMode::Variable));
+		System::set_block_number(3);
+		// 10 interest plus a 2% fee on 100 principal, paid by the borrower up front.
+		assert_noop!(LendingModule::extend_loan(RuntimeOrigin::signed(1), 0, 0, 5), pallet_assets::Error::<Test>::InsufficientBalance);
+		assert_ok!(<Assets as AssetManager>::mint(1, 1, 12));
+		assert_ok!(LendingModule::extend_loan(RuntimeOrigin::signed(1), 0, 0, 5));
+		let loan = LendingModule::loans(0, 0).unwrap();
+		assert_eq!((loan.principal, loan.interest, loan.maturity()), (100, 0, 16));
+		assert_eq!(Assets::balances((1, 1)), 0);
+		assert_eq!(Assets::balances((1, PoolAccount::get())), 12);
+		assert_eq!(LendingModule::reserves(0, 1), 12);
+		assert_noop!(LendingModule::extend_loan(RuntimeOrigin::signed(1), 0, 0, 0), Error::<Test>::ZeroExtension);
+		assert_noop!(LendingModule::extend_loan(RuntimeOrigin::signed(2), 0, 0, 5), Error::<Test>::NotLoanBorrower);
+
+		// Only the principal is left to repay.
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(1), 0, 0));
+		assert_eq!(LendingModule::reserves(0, 1), 12);
+	});
+}
+
+#[test]
+fn it_carries_interest_across_refinance() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 100, 10, RateMode::Variable));
+		System::set_block_number(5);
+		// 4 blocks at 5% leaves 120 owed.
+		assert_noop!(
+			LendingModule::refinance(RuntimeOrigin::signed(1), 0, 0, LoanTerms { amount: 119, duration: 30 }),
+			Error::<Test>::InsufficientRefinanceAmount
+		);
+		assert_ok!(LendingModule::refinance(RuntimeOrigin::signed(1), 0, 0, LoanTerms { amount: 120, duration: 30 }));
+
+		assert!(LendingModule::loans(0, 0).is_none());
+		let loan = LendingModule::loans(0, 1).unwrap();
+		assert_eq!((loan.principal, loan.interest, loan.maturity()), (120, 0, 35));
+		assert_eq!(LendingModule::reserves(0, 1), 20);
+		assert_eq!(<LendingModule as Lending>::loans_of(0, 1), vec![1]);
+
+		// Interest now accrues on the refinanced principal, old interest included.
+		System::set_block_number(6);
+		assert_eq!(<LendingModule as Lending>::amount_owed(0, 1), 126);
+	});
+}
+
+#[test]
+fn it_configures_pools_and_markets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(LendingModule::create_pool(RuntimeOrigin::signed(1)), BadOrigin);
+		assert_noop!(LendingModule::set_market(RuntimeOrigin::root(), 0, 1, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES), Error::<Test>::PoolNotFound);
+		assert_eq!(create_pool(), 0);
+		assert_eq!(create_pool(), 1);
+		assert_noop!(
+			LendingModule::set_market(RuntimeOrigin::root(), 0, 3, Permill::from_percent(90), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES),
+			Error::<Test>::InvalidRiskParameters
+		);
+		assert_noop!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 3, 100), Error::<Test>::MarketNotFound);
+
+		// Utilization drives the borrow rate.
+		let rate_model = RateModel { base_rate: Permill::from_percent(2), slope: Permill::from_percent(10) };
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 1, Permill::from_percent(50), Permill::from_percent(80), rate_model, ALL_TO_RESERVES));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 1_000));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 500, 10, RateMode::Variable));
+		assert_eq!(LendingModule::borrow_rate(0, 1), Permill::from_percent(7));
+	});
+}
+
+#[test]
+fn it_caps_markets_per_pool() {
+	new_test_ext().execute_with(|| {
+		// Assets 1, 2 and 9 leave room for one more market under the cap of four.
+		create_pool();
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 3, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES));
+		assert_noop!(
+			LendingModule::set_market(RuntimeOrigin::root(), 0, 4, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES),
+			Error::<Test>::TooManyMarkets
+		);
+		// Listed markets can still be reconfigured, and other pools have their own cap.
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 3, Permill::from_percent(40), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES));
+		assert_eq!(create_pool(), 1);
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 1, 4, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES));
+	});
+}
+
+#[test]
+fn it_isolates_collateral_between_pools() {
+	new_funded_ext().execute_with(|| {
+		let other_pool = create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), other_pool, 1, 1_000));
+
+		// Collateral in another pool does not back debt in pool 0.
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(2), 0, 2, 100, 10, RateMode::Variable), Error::<Test>::InsufficientCollateral);
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(2), other_pool, 1, 100, 10, RateMode::Variable));
+		assert_eq!(<LendingModule as Lending>::loans_of(0, 2), Vec::<LoanId>::new());
+		assert_eq!(<LendingModule as Lending>::loans_of(other_pool, 2), vec![0]);
+
+		// Debt in one pool does not count against collateral in another, and each pool numbers
+		// its loans from zero.
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 500, 10, RateMode::Variable));
+		assert_eq!(<LendingModule as Lending>::loans_of(0, 1), vec![0]);
+		assert_eq!((LendingModule::next_loan_id(0), LendingModule::next_loan_id(other_pool)), (1, 1));
+		assert_eq!(LendingModule::account_position(other_pool, &1).unwrap(), AccountPosition::default());
+	});
+}
+
+#[test]
+fn it_enforces_collateral_and_liquidity() {
+	new_funded_ext().execute_with(|| {
+		// 1_000 of collateral at a 50% collateral factor.
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 501, 10, RateMode::Variable), Error::<Test>::InsufficientCollateral);
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 400, 10, RateMode::Variable));
+		assert_eq!(
+			LendingModule::account_position(0, &1).unwrap(),
+			AccountPosition { borrowing_power: 500, liquidation_collateral: 800, debt: 400 }
+		);
+		assert_noop!(LendingModule::withdraw(RuntimeOrigin::signed(1), 0, 1, 201), Error::<Test>::InsufficientCollateral);
+		assert_ok!(LendingModule::withdraw(RuntimeOrigin::signed(1), 0, 1, 200));
+
+		// Only unborrowed deposits can leave the pool.
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(3), 0, 1, 10_000));
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(3), 0, 2, 700, 10, RateMode::Variable), Error::<Test>::InsufficientLiquidity);
+		assert_noop!(LendingModule::withdraw(RuntimeOrigin::signed(9), 0, 2, 700), Error::<Test>::InsufficientLiquidity);
+
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(4), 0, 9, 100));
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(4), 0, 1, 10, 10, RateMode::Variable), Error::<Test>::PriceUnavailable);
+	});
+}
+
+#[test]
+fn it_boosts_collateral_in_emode() {
+	new_funded_ext().execute_with(|| {
+		assert_noop!(
+			LendingModule::set_emode_category(RuntimeOrigin::root(), 0, 1, Permill::from_percent(96), Permill::from_percent(95)),
+			Error::<Test>::InvalidRiskParameters
+		);
+		assert_noop!(LendingModule::set_asset_emode_category(RuntimeOrigin::root(), 0, 1, Some(1)), Error::<Test>::EModeCategoryNotFound);
+		assert_ok!(LendingModule::set_emode_category(RuntimeOrigin::root(), 0, 1, Permill::from_percent(90), Permill::from_percent(95)));
+		assert_ok!(LendingModule::set_asset_emode_category(RuntimeOrigin::root(), 0, 1, Some(1)));
+		assert_noop!(LendingModule::set_emode(RuntimeOrigin::signed(1), 0, Some(2)), Error::<Test>::EModeCategoryNotFound);
+
+		// Entering is refused while a loan outside the category is open.
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 100, 10, RateMode::Variable));
+		assert_noop!(LendingModule::set_emode(RuntimeOrigin::signed(1), 0, Some(1)), Error::<Test>::AssetNotInEModeCategory);
+		assert_ok!(LendingModule::set_asset_emode_category(RuntimeOrigin::root(), 0, 2, Some(1)));
+		assert_ok!(LendingModule::set_emode(RuntimeOrigin::signed(1), 0, Some(1)));
+		assert_eq!(
+			LendingModule::account_position(0, &1).unwrap(),
+			AccountPosition { borrowing_power: 900, liquidation_collateral: 950, debt: 100 }
+		);
+
+		// Category assets can be borrowed up to the boosted factor, others not at all.
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 800, 10, RateMode::Variable));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(9), 0, 9, 100));
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 9, 10, 10, RateMode::Variable), Error::<Test>::AssetNotInEModeCategory);
+
+		// Leaving would drop borrowing power back to 500 against 900 of debt.
+		assert_noop!(LendingModule::set_emode(RuntimeOrigin::signed(1), 0, None), Error::<Test>::InsufficientCollateral);
+		assert_eq!(LendingModule::account_emode(0, 1), Some(1));
+	});
+}
+
+#[test]
+fn it_checks_delegated_loans_before_entering_emode() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::set_emode_category(RuntimeOrigin::root(), 0, 1, Permill::from_percent(90), Permill::from_percent(95)));
+		assert_ok!(LendingModule::set_asset_emode_category(RuntimeOrigin::root(), 0, 1, Some(1)));
+
+		// Account 1 has no loans of its own, but backs a delegatee's loan outside the category.
+		assert_ok!(LendingModule::approve_delegation(RuntimeOrigin::signed(1), 0, 2, 2, 100));
+		assert_ok!(LendingModule::borrow_delegated(RuntimeOrigin::signed(2), 0, 1, 2, 100, 10, RateMode::Variable));
+		assert_noop!(LendingModule::set_emode(RuntimeOrigin::signed(1), 0, Some(1)), Error::<Test>::AssetNotInEModeCategory);
+
+		assert_ok!(LendingModule::set_asset_emode_category(RuntimeOrigin::root(), 0, 2, Some(1)));
+		assert_ok!(LendingModule::set_emode(RuntimeOrigin::signed(1), 0, Some(1)));
+		assert_eq!(LendingModule::account_emode(0, 1), Some(1));
+	});
+}
+
+#[test]
+fn it_reports_account_health_and_rates() {
+	new_funded_ext().execute_with(|| {
+		assert_eq!(LendingModule::health_factor(0, &1).unwrap(), FixedU128::max_value());
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 400, 10, RateMode::Variable));
+		assert_eq!(LendingModule::account_liquidity(0, &1).unwrap(), AccountLiquidity { liquidity: 100, shortfall: 0 });
+		assert_eq!(LendingModule::health_factor(0, &1).unwrap(), FixedU128::saturating_from_rational(800, 400));
+
+		// 40% utilization at a flat 5% borrow rate, half of which is kept as reserves.
+		assert_eq!(LendingModule::borrow_rate(0, 2), Permill::from_percent(5));
+		assert_eq!(LendingModule::supply_rate(0, 2), Permill::zero());
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 2, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, Permill::from_percent(50)));
+		assert_eq!(LendingModule::supply_rate(0, 2), Permill::from_percent(1));
+
+		// Accrued interest eats into the headroom until the position is short.
+		System::set_block_number(6);
+		assert_eq!(LendingModule::account_liquidity(0, &1).unwrap(), AccountLiquidity { liquidity: 0, shortfall: 0 });
+		System::set_block_number(7);
+		assert_eq!(LendingModule::account_liquidity(0, &1).unwrap(), AccountLiquidity { liquidity: 0, shortfall: 20 });
+	});
+}
+
+#[test]
+fn it_executes_stop_loss_when_health_drops() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 400, 100, RateMode::Variable));
+		let stop_loss = Instruction::StopLoss { threshold: FixedU128::saturating_from_integer(2), collateral_asset: 1, loan_id: 0 };
+		assert_noop!(LendingModule::set_instruction(RuntimeOrigin::signed(2), 0, stop_loss.clone()), Error::<Test>::NotLoanBorrower);
+		assert_ok!(LendingModule::set_instruction(RuntimeOrigin::signed(1), 0, stop_loss));
+
+		// Exactly at the threshold nothing happens.
+		LendingModule::on_idle(1, Weight::MAX);
+		assert!(LendingModule::instructions(0).is_some());
+
+		// One block of interest pushes the health factor below two.
+		System::set_block_number(2);
+		LendingModule::on_idle(2, Weight::MAX);
+		assert!(LendingModule::instructions(0).is_none());
+		assert_eq!(LendingModule::instruction_count(1), 0);
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert_eq!(LendingModule::deposits((0, 1), 1), 580);
+		assert_eq!(LendingModule::markets(0, 1).unwrap().total_deposits, 1_580);
+		assert_eq!(LendingModule::reserves(0, 2), 20);
+	});
+}
+
+#[test]
+fn it_repays_at_maturity_in_bounded_batches() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(<Assets as AssetManager>::mint(1, 1, 100));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 10, 2, RateMode::Variable));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 10, 10, RateMode::Variable));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 1, 10, 10, RateMode::Variable));
+		for loan_id in 0..2 {
+			assert_ok!(LendingModule::set_instruction(RuntimeOrigin::signed(1), 0, Instruction::RepayAtMaturity { loan_id }));
+		}
+		assert_noop!(
+			LendingModule::set_instruction(RuntimeOrigin::signed(1), 0, Instruction::RepayAtMaturity { loan_id: 2 }),
+			Error::<Test>::TooManyInstructions
+		);
+		assert_noop!(LendingModule::cancel_instruction(RuntimeOrigin::signed(2), 1), Error::<Test>::NotInstructionOwner);
+
+		System::set_block_number(3);
+		LendingModule::on_idle(3, Weight::MAX);
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert!(LendingModule::loans(0, 1).is_some());
+		assert!(LendingModule::instructions(1).is_some());
+		assert_eq!(LendingModule::instruction_cursor(), 2);
+
+		// A loan repaid by hand leaves a stale instruction, dropped on the next pass.
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(1), 0, 1));
+		LendingModule::on_idle(4, Weight::MAX);
+		assert!(LendingModule::instructions(1).is_none());
+		assert_eq!(LendingModule::instruction_count(1), 0);
+	});
+}
+
+#[test]
+fn it_waits_for_funds_to_repay_at_maturity() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 100, 2, RateMode::Variable));
+		assert_ok!(LendingModule::set_instruction(RuntimeOrigin::signed(1), 0, Instruction::RepayAtMaturity { loan_id: 0 }));
+		System::set_block_number(3);
+		let owed = <LendingModule as Lending>::amount_owed(0, 0);
+
+		// A wallet one short of the debt leaves the loan and the instruction in place.
+		assert_ok!(<Assets as AssetManager>::mint(2, 1, owed - 1));
+		LendingModule::on_idle(3, Weight::MAX);
+		assert!(LendingModule::loans(0, 0).is_some());
+		assert!(LendingModule::instructions(0).is_some());
+		assert_eq!(Assets::balances((2, 1)), owed - 1);
+
+		assert_ok!(<Assets as AssetManager>::mint(2, 1, 1));
+		LendingModule::on_idle(3, Weight::MAX);
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert!(LendingModule::instructions(0).is_none());
+		assert_eq!(Assets::balances((2, 1)), 0);
+		assert_eq!(Assets::balances((2, PoolAccount::get())), owed);
+	});
+}
+
+#[test]
+fn it_liquidates_unhealthy_loan_for_liquidator() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 500, 100, RateMode::Variable));
+		assert_noop!(LendingModule::liquidate(RuntimeOrigin::signed(3), 0, 0), Error::<Test>::NotLiquidatable);
+
+		// Tightened risk parameters leave 400 of liquidation collateral against 500 of debt.
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 1, Permill::from_percent(30), Permill::from_percent(40), FLAT_FIVE_PERCENT, ALL_TO_RESERVES));
+		// The liquidator pays the debt, so it cannot liquidate without the loan's asset.
+		assert_noop!(LendingModule::liquidate(RuntimeOrigin::signed(3), 0, 0), pallet_assets::Error::<Test>::InsufficientBalance);
+		assert_ok!(<Assets as AssetManager>::mint(2, 3, 500));
+		assert_ok!(LendingModule::liquidate(RuntimeOrigin::signed(3), 0, 0));
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert_eq!(Assets::balances((2, 3)), 0);
+		assert_eq!(Assets::balances((2, PoolAccount::get())), 500);
+		// The debt plus a 10% penalty moves from borrower to liquidator.
+		assert_eq!(LendingModule::deposits((0, 1), 1), 450);
+		assert_eq!(LendingModule::deposits((0, 3), 1), 550);
+		assert_eq!(LendingModule::markets(0, 1).unwrap().total_deposits, 2_000);
+	});
+}
+
+fn open_overdue_loan() {
+	assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 100, 2, RateMode::Variable));
+	// Four blocks of interest, one block past maturity.
+	System::set_block_number(5);
+}
+
+#[test]
+fn it_liquidates_overdue_loans_unsigned() {
+	new_funded_ext().execute_with(|| {
+		open_overdue_loan();
+		let call = Call::liquidate_unsigned { block_number: 5, loans: vec![(0, 0)] };
+		assert!(LendingModule::validate_unsigned(TransactionSource::External, &call).is_ok());
+		assert_eq!(
+			LendingModule::validate_unsigned(TransactionSource::External, &Call::liquidate_unsigned { block_number: 5, loans: vec![(0, 1)] }),
+			InvalidTransaction::Custom(1).into()
+		);
+		assert_eq!(
+			LendingModule::validate_unsigned(TransactionSource::External, &Call::liquidate_unsigned { block_number: 6, loans: vec![(0, 0)] }),
+			InvalidTransaction::Future.into()
+		);
+		assert_eq!(
+			LendingModule::validate_unsigned(TransactionSource::External, &Call::liquidate_unsigned { block_number: 5, loans: vec![(0, 0); 3] }),
+			InvalidTransaction::ExhaustsResources.into()
+		);
+
+		assert_ok!(LendingModule::liquidate_unsigned(RuntimeOrigin::none(), 5, vec![(0, 0)]));
+		assert!(LendingModule::loans(0, 0).is_none());
+		// 120 owed plus a 12 penalty are sold out of the borrower's collateral.
+		assert_eq!(LendingModule::deposits((0, 1), 1), 868);
+		assert_eq!(LendingModule::markets(0, 1).unwrap().total_deposits, 1_868);
+		assert_eq!(LendingModule::reserves(0, 1), 12);
+		assert_eq!(LendingModule::reserves(0, 2), 20);
+
+		assert_eq!(LendingModule::next_unsigned_at(), 10);
+		assert_eq!(LendingModule::validate_unsigned(TransactionSource::External, &call), InvalidTransaction::Stale.into());
+	});
+}
+
+#[test]
+fn offchain_worker_submits_liquidations() {
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut ext = new_funded_ext();
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.execute_with(|| {
+		LendingModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		open_overdue_loan();
+		LendingModule::offchain_worker(5);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		assert_eq!(tx.call, RuntimeCall::LendingModule(Call::liquidate_unsigned { block_number: 5, loans: vec![(0, 0)] }));
+	});
+}
+
+#[test]
+fn it_borrows_against_delegated_collateral() {
+	new_funded_ext().execute_with(|| {
+		assert_noop!(LendingModule::approve_delegation(RuntimeOrigin::signed(1), 0, 1, 2, 300), Error::<Test>::SelfDelegation);
+		assert_ok!(LendingModule::approve_delegation(RuntimeOrigin::signed(1), 0, 2, 2, 300));
+		assert_noop!(LendingModule::borrow_delegated(RuntimeOrigin::signed(2), 0, 1, 2, 301, 10, RateMode::Variable), Error::<Test>::InsufficientDelegation);
+		assert_noop!(LendingModule::borrow_delegated(RuntimeOrigin::signed(3), 0, 1, 2, 100, 10, RateMode::Variable), Error::<Test>::InsufficientDelegation);
+		assert_ok!(LendingModule::borrow_delegated(RuntimeOrigin::signed(2), 0, 1, 2, 200, 10, RateMode::Variable));
+		assert_eq!(LendingModule::delegations((0, 1), (2, 2)), 100);
+
+		// The debt is the delegatee's, but it is backed by the delegator's position.
+		let loan = LendingModule::loans(0, 0).unwrap();
+		assert_eq!((loan.borrower, loan.delegator), (2, Some(1)));
+		assert_eq!(<LendingModule as Lending>::loans_of(0, 2), vec![0]);
+		assert_eq!(LendingModule::account_position(0, &2).unwrap().debt, 0);
+		assert_eq!(LendingModule::account_position(0, &1).unwrap().debt, 200);
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 301, 10, RateMode::Variable), Error::<Test>::InsufficientCollateral);
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 300, 10, RateMode::Variable));
+		assert_noop!(LendingModule::withdraw(RuntimeOrigin::signed(1), 0, 1, 1), Error::<Test>::InsufficientCollateral);
+
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(2), 0, 0));
+		assert!(!<DelegatedLoans<Test>>::contains_key((0, 1), 0));
+		assert_eq!(LendingModule::account_position(0, &1).unwrap().debt, 300);
+	});
+}
+
+#[test]
+fn it_liquidates_delegated_loans_from_delegator_collateral() {
+	new_funded_ext().execute_with(|| {
+		assert_ok!(LendingModule::approve_delegation(RuntimeOrigin::signed(1), 0, 2, 2, 500));
+		assert_ok!(LendingModule::borrow_delegated(RuntimeOrigin::signed(2), 0, 1, 2, 500, 100, RateMode::Variable));
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 1, Permill::from_percent(30), Permill::from_percent(40), FLAT_FIVE_PERCENT, ALL_TO_RESERVES));
+
+		assert_ok!(<Assets as AssetManager>::mint(2, 3, 500));
+		assert_ok!(LendingModule::liquidate(RuntimeOrigin::signed(3), 0, 0));
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert_eq!(LendingModule::deposits((0, 1), 1), 450);
+		assert_eq!(LendingModule::deposits((0, 3), 1), 550);
+	});
+}
+
+#[test]
+fn it_locks_stable_rates_and_rebalances_them() {
+	new_funded_ext().execute_with(|| {
+		let rate_model = RateModel { base_rate: Permill::from_percent(2), slope: Permill::from_percent(10) };
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 2, Permill::from_percent(50), Permill::from_percent(80), rate_model, ALL_TO_RESERVES));
+		// 20% utilization once the stable loan is out: 2% + 10% * 20%.
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 200, 100, RateMode::Stable));
+		let loan = LendingModule::loans(0, 0).unwrap();
+		assert_eq!((loan.rate_mode, loan.stable_rate), (RateMode::Stable, Permill::from_percent(4)));
+		assert_noop!(LendingModule::rebalance_stable_rate(RuntimeOrigin::signed(3), 0, 0), Error::<Test>::RebalanceNotNeeded);
+
+		// Utilization jumps to 50%; the variable loan pays the new rate, the stable one does not.
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 1, 1_000));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(2), 0, 2, 300, 100, RateMode::Variable));
+		assert_noop!(LendingModule::rebalance_stable_rate(RuntimeOrigin::signed(3), 0, 1), Error::<Test>::NotStableRate);
+		System::set_block_number(2);
+		assert_eq!(<LendingModule as Lending>::amount_owed(0, 0), 208);
+		assert_eq!(<LendingModule as Lending>::amount_owed(0, 1), 321);
+
+		// 7% against a locked 4% exceeds the 2% threshold.
+		assert_ok!(LendingModule::rebalance_stable_rate(RuntimeOrigin::signed(3), 0, 0));
+		let loan = LendingModule::loans(0, 0).unwrap();
+		assert_eq!((loan.interest, loan.stable_rate), (8, Permill::from_percent(7)));
+		System::set_block_number(3);
+		assert_eq!(<LendingModule as Lending>::amount_owed(0, 0), 222);
+	});
+}
+
+#[test]
+fn it_pays_interest_to_depositors_pro_rata() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(LendingModule::set_market(RuntimeOrigin::root(), 0, 2, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, Permill::from_percent(20)));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 1_000));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 2, 600));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(3), 0, 2, 200));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 400, 100, RateMode::Variable));
+		assert_noop!(LendingModule::claim_interest(RuntimeOrigin::signed(2), 0, 2), Error::<Test>::NoInterestToClaim);
+
+		// 100 of interest: 20 to reserves, 80 split 3:1 between the depositors.
+		System::set_block_number(6);
+		assert_ok!(LendingModule::repay(RuntimeOrigin::signed(1), 0, 0, 100));
+		assert_eq!(LendingModule::reserves(0, 2), 20);
+		assert_eq!(LendingModule::accrued_yield(0, &2, 2), 60);
+		assert_eq!(LendingModule::accrued_yield(0, &3, 2), 20);
+
+		// A late depositor earns nothing on interest paid before they joined.
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(4), 0, 2, 200));
+		assert_eq!(LendingModule::accrued_yield(0, &4, 2), 0);
+
+		// Claiming compounds: the yield joins the deposit and earns on the next payment.
+		assert_ok!(LendingModule::claim_interest(RuntimeOrigin::signed(2), 0, 2));
+		assert_eq!(LendingModule::deposits((0, 2), 2), 660);
+		assert_eq!(LendingModule::accrued_yield(0, &2, 2), 0);
+		assert_eq!(LendingModule::markets(0, 2).unwrap().total_deposits, 1_060);
+
+		// 40 more interest over two blocks on 400: 8 to reserves, 32 over 1_060 deposited, less the
+		// unit that does not divide evenly and is kept as reserves.
+		System::set_block_number(8);
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(1), 0, 0));
+		assert_eq!(LendingModule::reserves(0, 2), 29);
+		let yields: Vec<u64> = [2, 3, 4].iter().map(|who| LendingModule::accrued_yield(0, who, 2)).collect();
+		assert_eq!(yields, vec![19, 26, 6]);
+
+		// Everything borrowers paid is accounted for as yield or reserves.
+		let events: Vec<_> = System::events().into_iter().map(|record| record.event).collect();
+		let paid: u64 = events.iter().filter_map(|event| match event {
+			RuntimeEvent::LendingModule(Event::Repaid(_, _, _, _, interest_paid, _)) => Some(*interest_paid),
+			_ => None,
+		}).sum();
+		let claimed: u64 = events.iter().filter_map(|event| match event {
+			RuntimeEvent::LendingModule(Event::InterestClaimed(_, _, _, amount)) => Some(*amount),
+			_ => None,
+		}).sum();
+		assert_eq!((paid, claimed), (140, 60));
+		assert_eq!(claimed + yields.iter().sum::<u64>() + LendingModule::reserves(0, 2), paid);
+	});
+}
+
+#[test]
+fn it_pays_liquidity_mining_rewards() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_noop!(LendingModule::set_reward_speed(RuntimeOrigin::signed(1), 0, 2, 10, 20), BadOrigin);
+		assert_ok!(LendingModule::set_reward_speed(RuntimeOrigin::root(), 0, 2, 10, 20));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 1_000));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 2, 600));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(3), 0, 2, 200));
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 400, 100, RateMode::Variable));
+		assert_ok!(<Assets as AssetManager>::mint(100, 1_000, 100));
+
+		// Four blocks emit 40 to suppliers, split 3:1, and 80 to the only borrower.
+		System::set_block_number(5);
+		assert_ok!(LendingModule::claim_rewards(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Assets::balances((100, 1)), 80);
+		assert_eq!(LendingModule::accrued_rewards(0, 1), 0);
+
+		// The reward account only has 20 left, so the rest stays claimable.
+		assert_ok!(LendingModule::claim_rewards(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Assets::balances((100, 2)), 20);
+		assert_eq!(LendingModule::accrued_rewards(0, 2), 10);
+		assert_noop!(LendingModule::claim_rewards(RuntimeOrigin::signed(2), 0), Error::<Test>::RewardsDepleted);
+		assert_noop!(LendingModule::claim_rewards(RuntimeOrigin::signed(4), 0), Error::<Test>::NoRewardsToClaim);
+
+		// Repaying stops borrow rewards; supply rewards keep flowing.
+		assert_ok!(LendingModule::repay_all(RuntimeOrigin::signed(1), 0, 0));
+		assert_ok!(<Assets as AssetManager>::mint(100, 1_000, 100));
+		System::set_block_number(7);
+		assert_noop!(LendingModule::claim_rewards(RuntimeOrigin::signed(1), 0), Error::<Test>::NoRewardsToClaim);
+		assert_ok!(LendingModule::claim_rewards(RuntimeOrigin::signed(3), 0));
+		assert_eq!(Assets::balances((100, 3)), 15);
+	});
+}
+
+/// Account 1 borrows 50 of asset 2 against 100 of asset 1 for a single block, so interest
+/// soon outgrows the collateral.
+fn open_insolvent_loan() {
+	assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(1), 0, 1, 100));
+	assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 50, 1, RateMode::Variable));
+}
+
+#[test]
+fn it_covers_bad_debt_from_insurance_then_depositors() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 2, 600));
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(3), 0, 2, 400));
+		open_insolvent_loan();
+
+		// 50 of interest lands in reserves, 30 of which is moved to the insurance fund.
+		System::set_block_number(21);
+		assert_ok!(LendingModule::repay(RuntimeOrigin::signed(1), 0, 0, 50));
+		assert_noop!(LendingModule::fund_insurance(RuntimeOrigin::signed(1), 0, 2, 30), BadOrigin);
+		assert_noop!(LendingModule::fund_insurance(RuntimeOrigin::root(), 0, 2, 51), Error::<Test>::InsufficientReserves);
+		assert_ok!(LendingModule::fund_insurance(RuntimeOrigin::root(), 0, 2, 30));
+		assert_eq!(LendingModule::insurance_fund(0, 2), 30);
+
+		// 150 owed against 100 of collateral: seizing it all pays the interest and leaves 50
+		// of principal, 30 covered by insurance and 20 borne by depositors.
+		System::set_block_number(61);
+		assert_ok!(<Assets as AssetManager>::mint(2, 4, 100));
+		assert_ok!(LendingModule::liquidate(RuntimeOrigin::signed(4), 0, 0));
+		assert_eq!(Assets::balances((2, 4)), 0);
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert_eq!(LendingModule::insurance_fund(0, 2), 0);
+		let market = LendingModule::markets(0, 2).unwrap();
+		assert_eq!(market.total_borrows, 0);
+		assert_eq!(market.total_deposits, 980);
+		assert_eq!(market.exchange_rate, FixedU128::saturating_from_rational(98, 100));
+
+		// Depositors lose pro rata, applied the next time their deposit is touched.
+		assert_eq!(LendingModule::deposit_balance(0, &2, 2), 588);
+		assert_eq!(LendingModule::deposit_balance(0, &3, 2), 392);
+		assert_noop!(LendingModule::withdraw(RuntimeOrigin::signed(2), 0, 2, 589), Error::<Test>::InsufficientBalance);
+		assert_ok!(LendingModule::withdraw(RuntimeOrigin::signed(2), 0, 2, 588));
+		assert_eq!(LendingModule::deposits((0, 2), 2), 0);
+		assert_eq!(LendingModule::markets(0, 2).unwrap().total_deposits, 392);
+	});
+}
+
+#[test]
+fn it_starts_a_new_epoch_when_a_loss_wipes_out_a_market() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 2, 50));
+		open_insolvent_loan();
+
+		System::set_block_number(41);
+		assert_ok!(<Assets as AssetManager>::mint(2, 4, 1_000));
+		assert_ok!(LendingModule::liquidate(RuntimeOrigin::signed(4), 0, 0));
+		let market = LendingModule::markets(0, 2).unwrap();
+		assert_eq!(market.total_deposits, 0);
+		assert_eq!(market.epoch, 1);
+		assert_eq!(LendingModule::deposit_balance(0, &2, 2), 0);
+
+		// Later deposits are unaffected by the old epoch's loss.
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(3), 0, 2, 10));
+		assert_eq!(LendingModule::deposit_balance(0, &3, 2), 10);
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 2, 10));
+		assert_eq!(LendingModule::deposit_balance(0, &2, 2), 10);
+	});
+}
+
+#[test]
+fn it_sells_pledged_items_by_dutch_auction() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(LendingModule::deposit(RuntimeOrigin::signed(2), 0, 2, 1_000));
+		TestItems::give(7, 1);
+		assert_noop!(LendingModule::pledge_item(RuntimeOrigin::signed(1), 0, 7), Error::<Test>::ItemNotAppraised);
+		let appraisal = ItemAppraisal { value: 200, collateral_factor: Permill::from_percent(50), liquidation_threshold: Permill::from_percent(80) };
+		assert_ok!(LendingModule::appraise_item(RuntimeOrigin::root(), 0, 7, appraisal));
+		assert_noop!(LendingModule::pledge_item(RuntimeOrigin::signed(3), 0, 7), Error::<Test>::NotItemOwner);
+		assert_ok!(LendingModule::pledge_item(RuntimeOrigin::signed(1), 0, 7));
+		assert_eq!(TestItems::owner(&7), Some(999));
+
+		// The item alone backs the loan, so it cannot be released while the loan is open.
+		assert_noop!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 101, 1, RateMode::Variable), Error::<Test>::InsufficientCollateral);
+		assert_ok!(LendingModule::borrow(RuntimeOrigin::signed(1), 0, 2, 100, 1, RateMode::Variable));
+		assert_noop!(LendingModule::release_item(RuntimeOrigin::signed(1), 0, 7), Error::<Test>::InsufficientCollateral);
+		assert_noop!(LendingModule::auction_item(RuntimeOrigin::signed(4), 0, 0, 7), Error::<Test>::NotLiquidatable);
+
+		// Overdue, but the price could fall to nothing until governance sets a floor.
+		System::set_block_number(3);
+		assert_noop!(LendingModule::auction_item(RuntimeOrigin::signed(4), 0, 0, 7), Error::<Test>::AuctionFloorNotSet);
+		assert_noop!(LendingModule::set_auction_floor(RuntimeOrigin::signed(1), 0, Permill::from_percent(50)), BadOrigin);
+		assert_noop!(LendingModule::set_auction_floor(RuntimeOrigin::root(), 0, Permill::zero()), Error::<Test>::InvalidRiskParameters);
+		assert_ok!(LendingModule::set_auction_floor(RuntimeOrigin::root(), 0, Permill::from_percent(50)));
+
+		// The price starts 20% above the appraisal and never falls below half of it.
+		assert_ok!(LendingModule::auction_item(RuntimeOrigin::signed(4), 0, 0, 7));
+		let auction = LendingModule::auctions(0, 7).unwrap();
+		assert_eq!((auction.start_price, auction.floor_price), (240, 100));
+		assert_eq!(auction.price_at(20, AuctionPriceDecay::get()), 100);
+		assert_noop!(LendingModule::auction_item(RuntimeOrigin::signed(4), 0, 0, 7), Error::<Test>::AuctionInProgress);
+		assert_noop!(LendingModule::release_item(RuntimeOrigin::signed(1), 0, 7), Error::<Test>::AuctionInProgress);
+		assert_noop!(LendingModule::cancel_auction(RuntimeOrigin::signed(1), 0, 7), Error::<Test>::AuctionStillNeeded);
+
+		// Two blocks later the price is 192, which the bidder must be able to pay.
+		System::set_block_number(5);
+		assert_ok!(<Assets as AssetManager>::mint(2, 4, 500));
+		assert_noop!(LendingModule::bid(RuntimeOrigin::signed(3), 0, 7), pallet_assets::Error::<Test>::InsufficientBalance);
+		assert_ok!(LendingModule::bid(RuntimeOrigin::signed(4), 0, 7));
+		assert_eq!(TestItems::owner(&7), Some(4));
+		assert_eq!(Assets::balances((2, 4)), 308);
+
+		// The payment backs the repayment of 120 and the 72 deposited back for the owner.
+		assert_eq!(Assets::balances((2, PoolAccount::get())), 192);
+		assert!(LendingModule::loans(0, 0).is_none());
+		assert_eq!(LendingModule::deposits((0, 1), 2), 72);
+		assert!(!<ItemCollateral<Test>>::contains_key((0, 1), 7));
+		assert_noop!(LendingModule::bid(RuntimeOrigin::signed(3), 0, 7), Error::<Test>::AuctionNotFound);
+	});
+}
+
+#[test]
+fn it_lists_genesis_markets() {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	GenesisConfig::<Test> {
+		markets: vec![
+			(0, 1, Permill::from_percent(50), Permill::from_percent(80), FLAT_FIVE_PERCENT, ALL_TO_RESERVES),
+			(2, 1, Permill::from_percent(60), Permill::from_percent(70), FLAT_FIVE_PERCENT, Permill::zero()),
+		],
+		reserves: vec![(0, 1, 25)],
+		deposits: vec![(2, 7, 1, 500)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		assert!(LendingModule::pools(0) && LendingModule::pools(2));
+		assert_eq!(LendingModule::next_pool_id(), 3);
+		assert_eq!(LendingModule::markets(2, 1).unwrap().collateral_factor, Permill::from_percent(60));
+		assert_eq!(LendingModule::reserves(0, 1), 25);
+		assert_eq!(LendingModule::deposits((2, 7), 1), 500);
+		assert_eq!(LendingModule::markets(2, 1).unwrap().total_deposits, 500);
+	});
 }
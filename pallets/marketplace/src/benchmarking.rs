@@ -0,0 +1,124 @@
+//! Benchmarking setup for pallet-marketplace
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Marketplace;
+use frame_benchmarking::v2::*;
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use pallet_assets::{AssetManager, AssetMetadata};
+use pallet_cross_game_item_usage::CrossGameItemUsage;
+
+/// Register token 1 and give `who` enough of it to pay `price`.
+fn fund<T: Config>(who: &T::AccountId, price: T::Balance)
+where
+	T::AssetId: From<u32>,
+{
+	T::Assets::register_asset(1u32.into(), AssetMetadata::default()).expect("asset registers");
+	T::Assets::mint(1u32.into(), who.clone(), price).expect("asset mints");
+}
+
+/// Register game 1 with the maximum royalty and issue item 1 to `owner`, so sales pay out royalties.
+fn item_with_royalty<T: Config>(owner: &T::AccountId) -> T::GameItemId
+where
+	T::GameItemId: From<u32>,
+{
+	let creator: T::AccountId = account("creator", 0, 0);
+	let item_id: T::GameItemId = 1u32.into();
+	T::BenchmarkHelper::create_game(1, &creator);
+	T::BenchmarkHelper::create_item(1, item_id, owner);
+	Royalties::<T>::insert(1, T::MaxRoyalty::get());
+	item_id
+}
+
+#[benchmarks(where T::GameItemId: From<u32>, T::AssetId: From<u32>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_royalty() {
+		let caller: T::AccountId = whitelisted_caller();
+		T::BenchmarkHelper::create_game(1, &caller);
+		let royalty = T::MaxRoyalty::get();
+		#[extrinsic_call]
+		set_royalty(RawOrigin::Signed(caller), 1, royalty);
+
+		assert_eq!(Royalties::<T>::get(1), royalty);
+	}
+
+	#[benchmark]
+	fn list_item() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = item_with_royalty::<T>(&caller);
+		fund::<T>(&caller, 0u32.into());
+		#[extrinsic_call]
+		list_item(RawOrigin::Signed(caller), 1, item_id, 1u32.into(), 100u32.into());
+
+		assert!(Listings::<T>::contains_key((1, item_id)));
+	}
+
+	#[benchmark]
+	fn cancel_listing() {
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = item_with_royalty::<T>(&caller);
+		Listings::<T>::insert((1, item_id), Listing { seller: caller.clone(), asset_id: 1u32.into(), price: 100u32.into() });
+		#[extrinsic_call]
+		cancel_listing(RawOrigin::Signed(caller), 1, item_id);
+
+		assert!(!Listings::<T>::contains_key((1, item_id)));
+	}
+
+	#[benchmark]
+	fn buy() {
+		let seller: T::AccountId = account("seller", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = item_with_royalty::<T>(&seller);
+		fund::<T>(&caller, 100u32.into());
+		Listings::<T>::insert((1, item_id), Listing { seller, asset_id: 1u32.into(), price: 100u32.into() });
+		#[extrinsic_call]
+		buy(RawOrigin::Signed(caller.clone()), 1, item_id);
+
+		assert_eq!(T::Items::owner_of(1, item_id), Some(caller));
+	}
+
+	#[benchmark]
+	fn make_offer() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = item_with_royalty::<T>(&owner);
+		fund::<T>(&caller, 100u32.into());
+		#[extrinsic_call]
+		make_offer(RawOrigin::Signed(caller.clone()), 1, item_id, 1u32.into(), 100u32.into());
+
+		assert!(Offers::<T>::contains_key((1, item_id), caller));
+	}
+
+	#[benchmark]
+	fn withdraw_offer() {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		let item_id = item_with_royalty::<T>(&owner);
+		fund::<T>(&T::EscrowAccount::get(), 100u32.into());
+		Offers::<T>::insert((1, item_id), &caller, Offer { asset_id: 1u32.into(), amount: 100u32.into() });
+		#[extrinsic_call]
+		withdraw_offer(RawOrigin::Signed(caller.clone()), 1, item_id);
+
+		assert!(!Offers::<T>::contains_key((1, item_id), caller));
+	}
+
+	#[benchmark]
+	fn accept_offer() {
+		let caller: T::AccountId = whitelisted_caller();
+		let bidder: T::AccountId = account("bidder", 0, 0);
+		let item_id = item_with_royalty::<T>(&caller);
+		fund::<T>(&T::EscrowAccount::get(), 100u32.into());
+		Offers::<T>::insert((1, item_id), &bidder, Offer { asset_id: 1u32.into(), amount: 100u32.into() });
+		#[extrinsic_call]
+		accept_offer(RawOrigin::Signed(caller), 1, item_id, bidder.clone());
+
+		assert_eq!(T::Items::owner_of(1, item_id), Some(bidder));
+	}
+
+	impl_benchmark_test_suite!(Marketplace, crate::mock::new_test_ext(), crate::mock::Test);
+}
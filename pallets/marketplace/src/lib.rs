@@ -0,0 +1,328 @@
+//! # Marketplace Pallet
+//!
+//! Lets players trade the items tracked by the cross-game item registry for any pallet-assets
+//! token. Owners list items at a fixed price, and anyone may buy a listed item outright. Players
+//! may also place offers on any item; the offered tokens are held in escrow until the owner
+//! accepts the offer or the bidder withdraws it.
+//!
+//! Every sale settles atomically: the item changes hands and the payment is split between the
+//! seller and the game's owner, who may set a creator royalty taken from the price of every sale
+//! of the game's items.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// An item offered for sale at a fixed price.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Listing<AccountId, AssetId, Balance> {
+	pub seller: AccountId,
+	pub asset_id: AssetId,
+	pub price: Balance,
+}
+
+/// A bid on an item, held in escrow until accepted or withdrawn.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Offer<AssetId, Balance> {
+	pub asset_id: AssetId,
+	pub amount: Balance,
+}
+
+/// Sets up the state benchmarks need from the pallets the marketplace builds on.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, GameItemId> {
+	/// Register `game_id` with `owner` as its owner.
+	fn create_game(game_id: u32, owner: &AccountId);
+	/// Issue `item_id` in `game_id` to `owner`.
+	fn create_item(game_id: u32, item_id: GameItemId, owner: &AccountId);
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_assets::AssetManager;
+	use pallet_cross_game_item_usage::CrossGameItemUsage;
+	use sp_runtime::{
+		traits::{AtLeast32BitUnsigned, Saturating, Zero},
+		Permill,
+	};
+
+	pub type ListingOf<T> =
+		Listing<<T as frame_system::Config>::AccountId, <T as Config>::AssetId, <T as Config>::Balance>;
+	pub type OfferOf<T> = Offer<<T as Config>::AssetId, <T as Config>::Balance>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching runtime event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Identifier of an item within a game.
+		type GameItemId: Parameter + Member + MaxEncodedLen + Copy;
+		/// Identifier of a token sales are paid in.
+		type AssetId: Parameter + Member + MaxEncodedLen + Copy;
+		/// Token amounts.
+		type Balance: Parameter + Member + MaxEncodedLen + AtLeast32BitUnsigned + Copy;
+		/// The tokens sales are paid in.
+		type Assets: AssetManager<AssetId = Self::AssetId, AccountId = Self::AccountId, Balance = Self::Balance>;
+		/// The registry that tracks which account owns each item.
+		type Items: CrossGameItemUsage<GameItemId = Self::GameItemId, AccountId = Self::AccountId>;
+		/// Account holding the tokens of open offers.
+		#[pallet::constant]
+		type EscrowAccount: Get<Self::AccountId>;
+		/// Largest royalty a game owner may take from a sale.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
+		/// Weight information for the extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+		/// Creates the games and items benchmarks trade.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::GameItemId>;
+	}
+
+	/// Share of every sale of a game's items paid to the game's owner.
+	#[pallet::storage]
+	#[pallet::getter(fn royalties)]
+	pub type Royalties<T: Config> = StorageMap<_, Blake2_128Concat, u32, Permill, ValueQuery>;
+
+	/// Items for sale, keyed by game and item.
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> = StorageMap<_, Blake2_128Concat, (u32, T::GameItemId), ListingOf<T>>;
+
+	/// Open offers, keyed by game and item, then bidder.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (u32, T::GameItemId), Blake2_128Concat, T::AccountId, OfferOf<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A game's owner changed the royalty taken from sales of its items.
+		RoyaltySet { game_id: u32, royalty: Permill },
+		/// An item was listed for sale.
+		Listed { game_id: u32, item_id: T::GameItemId, seller: T::AccountId, asset_id: T::AssetId, price: T::Balance },
+		/// A listing was withdrawn.
+		ListingCancelled { game_id: u32, item_id: T::GameItemId },
+		/// A bidder placed an offer on an item.
+		OfferMade { game_id: u32, item_id: T::GameItemId, bidder: T::AccountId, asset_id: T::AssetId, amount: T::Balance },
+		/// A bidder withdrew an offer and got the escrowed tokens back.
+		OfferWithdrawn { game_id: u32, item_id: T::GameItemId, bidder: T::AccountId },
+		/// An item was sold. `royalty` of `price` went to the game's owner, the rest to the seller.
+		Sold {
+			game_id: u32,
+			item_id: T::GameItemId,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			asset_id: T::AssetId,
+			price: T::Balance,
+			royalty: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The game is not registered.
+		GameNotFound,
+		/// Only the game's owner may do this.
+		NotGameOwner,
+		/// The royalty exceeds the configured maximum.
+		RoyaltyTooHigh,
+		/// The item does not exist.
+		ItemNotFound,
+		/// Only the item's owner may do this.
+		NotItemOwner,
+		/// The token is not registered with pallet-assets.
+		UnknownAsset,
+		/// Prices and offers must be above zero.
+		ZeroPrice,
+		/// The item is not listed for sale.
+		NotListed,
+		/// The seller no longer owns the listed item.
+		ListingStale,
+		/// Owners cannot buy or bid on their own items.
+		BuyerIsSeller,
+		/// The bidder already has an offer on this item.
+		OfferExists,
+		/// The bidder has no offer on this item.
+		OfferNotFound,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the share of every sale of `game_id`'s items paid to the caller, the game's owner.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_royalty())]
+		pub fn set_royalty(origin: OriginFor<T>, game_id: u32, royalty: Permill) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let owner = T::Items::game_owner(game_id).ok_or(Error::<T>::GameNotFound)?;
+			ensure!(owner == sender, Error::<T>::NotGameOwner);
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+			if royalty.is_zero() {
+				Royalties::<T>::remove(game_id);
+			} else {
+				Royalties::<T>::insert(game_id, royalty);
+			}
+			Self::deposit_event(Event::RoyaltySet { game_id, royalty });
+			Ok(())
+		}
+
+		/// Offer one of the caller's items for sale at `price` in `asset_id`, replacing any earlier
+		/// listing of the item.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::list_item())]
+		pub fn list_item(
+			origin: OriginFor<T>,
+			game_id: u32,
+			item_id: T::GameItemId,
+			asset_id: T::AssetId,
+			price: T::Balance,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			Self::ensure_item_owner(game_id, item_id, &seller)?;
+			ensure!(T::Assets::get_asset(asset_id).is_some(), Error::<T>::UnknownAsset);
+			ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+			Listings::<T>::insert((game_id, item_id), Listing { seller: seller.clone(), asset_id, price });
+			Self::deposit_event(Event::Listed { game_id, item_id, seller, asset_id, price });
+			Ok(())
+		}
+
+		/// Withdraw one of the caller's listings.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::cancel_listing())]
+		pub fn cancel_listing(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let listing = Listings::<T>::get((game_id, item_id)).ok_or(Error::<T>::NotListed)?;
+			ensure!(listing.seller == sender, Error::<T>::NotItemOwner);
+			Listings::<T>::remove((game_id, item_id));
+			Self::deposit_event(Event::ListingCancelled { game_id, item_id });
+			Ok(())
+		}
+
+		/// Buy a listed item at its listed price.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::buy())]
+		pub fn buy(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			let listing = Listings::<T>::get((game_id, item_id)).ok_or(Error::<T>::NotListed)?;
+			ensure!(listing.seller != buyer, Error::<T>::BuyerIsSeller);
+			ensure!(T::Items::owner_of(game_id, item_id).as_ref() == Some(&listing.seller), Error::<T>::ListingStale);
+			Self::settle(game_id, item_id, &listing.seller, &buyer, &buyer, listing.asset_id, listing.price)
+		}
+
+		/// Bid `amount` of `asset_id` for an item. The tokens are held in escrow until the owner
+		/// accepts the offer or the caller withdraws it.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			game_id: u32,
+			item_id: T::GameItemId,
+			asset_id: T::AssetId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+			let owner = T::Items::owner_of(game_id, item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(owner != bidder, Error::<T>::BuyerIsSeller);
+			ensure!(T::Assets::get_asset(asset_id).is_some(), Error::<T>::UnknownAsset);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroPrice);
+			ensure!(!Offers::<T>::contains_key((game_id, item_id), &bidder), Error::<T>::OfferExists);
+			T::Assets::transfer(asset_id, bidder.clone(), T::EscrowAccount::get(), amount)?;
+			Offers::<T>::insert((game_id, item_id), &bidder, Offer { asset_id, amount });
+			Self::deposit_event(Event::OfferMade { game_id, item_id, bidder, asset_id, amount });
+			Ok(())
+		}
+
+		/// Withdraw the caller's offer on an item and get the escrowed tokens back.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, game_id: u32, item_id: T::GameItemId) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+			let offer = Offers::<T>::take((game_id, item_id), &bidder).ok_or(Error::<T>::OfferNotFound)?;
+			T::Assets::transfer(offer.asset_id, T::EscrowAccount::get(), bidder.clone(), offer.amount)?;
+			Self::deposit_event(Event::OfferWithdrawn { game_id, item_id, bidder });
+			Ok(())
+		}
+
+		/// Sell one of the caller's items to `bidder` for the tokens they hold in escrow.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::accept_offer())]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			game_id: u32,
+			item_id: T::GameItemId,
+			bidder: T::AccountId,
+		) -> DispatchResult {
+			let seller = ensure_signed(origin)?;
+			Self::ensure_item_owner(game_id, item_id, &seller)?;
+			let offer = Offers::<T>::take((game_id, item_id), &bidder).ok_or(Error::<T>::OfferNotFound)?;
+			Self::settle(game_id, item_id, &seller, &bidder, &T::EscrowAccount::get(), offer.asset_id, offer.amount)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Ensure `who` owns the item.
+		fn ensure_item_owner(game_id: u32, item_id: T::GameItemId, who: &T::AccountId) -> DispatchResult {
+			let owner = T::Items::owner_of(game_id, item_id).ok_or(Error::<T>::ItemNotFound)?;
+			ensure!(owner == *who, Error::<T>::NotItemOwner);
+			Ok(())
+		}
+
+		/// The part of `price` owed to `game_id`'s owner, and the owner it is owed to.
+		pub fn royalty_of(game_id: u32, price: T::Balance) -> (T::Balance, Option<T::AccountId>) {
+			match T::Items::game_owner(game_id) {
+				Some(creator) => (Royalties::<T>::get(game_id).mul_floor(price), Some(creator)),
+				None => (Zero::zero(), None),
+			}
+		}
+
+		/// Hand the item from `seller` to `buyer` and pay `price` out of `payer`'s tokens, the royalty
+		/// to the game's owner and the rest to the seller. Any failure leaves nothing changed.
+		fn settle(
+			game_id: u32,
+			item_id: T::GameItemId,
+			seller: &T::AccountId,
+			buyer: &T::AccountId,
+			payer: &T::AccountId,
+			asset_id: T::AssetId,
+			price: T::Balance,
+		) -> DispatchResult {
+			T::Items::transfer_ownership(game_id, item_id, seller, buyer)?;
+			let (royalty, creator) = Self::royalty_of(game_id, price);
+			if let Some(creator) = creator.filter(|_| !royalty.is_zero()) {
+				T::Assets::transfer(asset_id, payer.clone(), creator, royalty)?;
+			}
+			T::Assets::transfer(asset_id, payer.clone(), seller.clone(), price.saturating_sub(royalty))?;
+			Listings::<T>::remove((game_id, item_id));
+			Self::deposit_event(Event::Sold {
+				game_id,
+				item_id,
+				seller: seller.clone(),
+				buyer: buyer.clone(),
+				asset_id,
+				price,
+				royalty,
+			});
+			Ok(())
+		}
+	}
+}
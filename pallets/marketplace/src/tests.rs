@@ -0,0 +1,136 @@
+use crate::{mock::*, Error, Event, Listing, Offer};
+use frame_support::{assert_noop, assert_ok};
+use pallet_assets::AssetManager;
+use pallet_cross_game_item_usage::{CrossGameItemUsage as _, GameMetadataOf};
+use sp_runtime::Permill;
+
+/// Register game 1, owned by account 10, and issue item 7 of it to account 1.
+fn setup_item() {
+	System::set_block_number(1);
+	let metadata = GameMetadataOf::<Test> { name: b"Test Game".to_vec().try_into().unwrap() };
+	assert_ok!(CrossGameItemUsage::register_game(RuntimeOrigin::signed(10), 1, metadata));
+	assert_ok!(CrossGameItemUsage::mint_item(RuntimeOrigin::signed(10), 1, 7, 0, 1, Default::default()));
+}
+
+fn tokens(who: u64) -> u64 {
+	TestAssets::balance(1, &who)
+}
+
+#[test]
+fn game_owner_sets_royalty() {
+	new_test_ext().execute_with(|| {
+		setup_item();
+		assert_noop!(Marketplace::set_royalty(RuntimeOrigin::signed(10), 2, Permill::from_percent(5)), Error::<Test>::GameNotFound);
+		assert_noop!(Marketplace::set_royalty(RuntimeOrigin::signed(1), 1, Permill::from_percent(5)), Error::<Test>::NotGameOwner);
+		assert_noop!(
+			Marketplace::set_royalty(RuntimeOrigin::signed(10), 1, Permill::from_percent(11)),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		assert_ok!(Marketplace::set_royalty(RuntimeOrigin::signed(10), 1, Permill::from_percent(5)));
+		System::assert_last_event(Event::RoyaltySet { game_id: 1, royalty: Permill::from_percent(5) }.into());
+		assert_eq!(Marketplace::royalties(1), Permill::from_percent(5));
+	});
+}
+
+#[test]
+fn buying_a_listed_item_pays_seller_and_creator() {
+	new_test_ext().execute_with(|| {
+		setup_item();
+		assert_ok!(Marketplace::set_royalty(RuntimeOrigin::signed(10), 1, Permill::from_percent(5)));
+		assert_ok!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 7, 1, 200));
+		System::assert_last_event(Event::Listed { game_id: 1, item_id: 7, seller: 1, asset_id: 1, price: 200 }.into());
+		assert_eq!(Marketplace::listings((1, 7)), Some(Listing { seller: 1, asset_id: 1, price: 200 }));
+
+		assert_noop!(Marketplace::buy(RuntimeOrigin::signed(1), 1, 7), Error::<Test>::BuyerIsSeller);
+		assert_ok!(Marketplace::buy(RuntimeOrigin::signed(2), 1, 7));
+		System::assert_last_event(
+			Event::Sold { game_id: 1, item_id: 7, seller: 1, buyer: 2, asset_id: 1, price: 200, royalty: 10 }.into(),
+		);
+		assert_eq!(CrossGameItemUsage::owner_of(1, 7), Some(2));
+		assert_eq!(CrossGameItemUsage::items_of(&2), vec![(1, 7)]);
+		assert_eq!(tokens(1), 1_190);
+		assert_eq!(tokens(2), 800);
+		assert_eq!(tokens(10), 10);
+		assert!(Marketplace::listings((1, 7)).is_none());
+		assert_noop!(Marketplace::buy(RuntimeOrigin::signed(3), 1, 7), Error::<Test>::NotListed);
+	});
+}
+
+#[test]
+fn listing_requires_owned_item_and_known_token() {
+	new_test_ext().execute_with(|| {
+		setup_item();
+		assert_noop!(Marketplace::list_item(RuntimeOrigin::signed(2), 1, 7, 1, 200), Error::<Test>::NotItemOwner);
+		assert_noop!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 8, 1, 200), Error::<Test>::ItemNotFound);
+		assert_noop!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 7, 2, 200), Error::<Test>::UnknownAsset);
+		assert_noop!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 7, 1, 0), Error::<Test>::ZeroPrice);
+
+		assert_ok!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 7, 1, 200));
+		assert_noop!(Marketplace::cancel_listing(RuntimeOrigin::signed(2), 1, 7), Error::<Test>::NotItemOwner);
+		assert_ok!(Marketplace::cancel_listing(RuntimeOrigin::signed(1), 1, 7));
+		System::assert_last_event(Event::ListingCancelled { game_id: 1, item_id: 7 }.into());
+		assert_noop!(Marketplace::cancel_listing(RuntimeOrigin::signed(1), 1, 7), Error::<Test>::NotListed);
+	});
+}
+
+#[test]
+fn listing_goes_stale_when_item_changes_hands() {
+	new_test_ext().execute_with(|| {
+		setup_item();
+		assert_ok!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 7, 1, 200));
+		assert_ok!(CrossGameItemUsage::transfer_ownership(RuntimeOrigin::signed(1), 1, 7, 3));
+		assert_noop!(Marketplace::buy(RuntimeOrigin::signed(2), 1, 7), Error::<Test>::ListingStale);
+	});
+}
+
+#[test]
+fn rented_items_cannot_be_sold() {
+	new_test_ext().execute_with(|| {
+		setup_item();
+		assert_ok!(Marketplace::list_item(RuntimeOrigin::signed(1), 1, 7, 1, 200));
+		assert_ok!(CrossGameItemUsage::list_for_rent(RuntimeOrigin::signed(1), 1, 7, 10, 5));
+		assert_ok!(CrossGameItemUsage::rent(RuntimeOrigin::signed(3), 1, 7, 2));
+		// Settlement is atomic: the buyer keeps their tokens when the item cannot move.
+		assert_noop!(
+			Marketplace::buy(RuntimeOrigin::signed(2), 1, 7),
+			pallet_cross_game_item_usage::Error::<Test>::ItemRented
+		);
+		assert_eq!(tokens(2), 1_000);
+	});
+}
+
+#[test]
+fn offers_are_escrowed_until_accepted_or_withdrawn() {
+	new_test_ext().execute_with(|| {
+		setup_item();
+		assert_ok!(Marketplace::set_royalty(RuntimeOrigin::signed(10), 1, Permill::from_percent(10)));
+		assert_noop!(Marketplace::make_offer(RuntimeOrigin::signed(1), 1, 7, 1, 100), Error::<Test>::BuyerIsSeller);
+		assert_noop!(Marketplace::make_offer(RuntimeOrigin::signed(2), 1, 7, 1, 2_000), "insufficient balance");
+
+		assert_ok!(Marketplace::make_offer(RuntimeOrigin::signed(2), 1, 7, 1, 100));
+		System::assert_last_event(Event::OfferMade { game_id: 1, item_id: 7, bidder: 2, asset_id: 1, amount: 100 }.into());
+		assert_eq!(Marketplace::offers((1, 7), 2), Some(Offer { asset_id: 1, amount: 100 }));
+		assert_eq!(tokens(2), 900);
+		assert_eq!(tokens(EscrowAccount::get()), 100);
+		assert_noop!(Marketplace::make_offer(RuntimeOrigin::signed(2), 1, 7, 1, 150), Error::<Test>::OfferExists);
+
+		assert_ok!(Marketplace::make_offer(RuntimeOrigin::signed(3), 1, 7, 1, 300));
+		assert_ok!(Marketplace::withdraw_offer(RuntimeOrigin::signed(2), 1, 7));
+		System::assert_last_event(Event::OfferWithdrawn { game_id: 1, item_id: 7, bidder: 2 }.into());
+		assert_eq!(tokens(2), 1_000);
+		assert_noop!(Marketplace::withdraw_offer(RuntimeOrigin::signed(2), 1, 7), Error::<Test>::OfferNotFound);
+
+		assert_noop!(Marketplace::accept_offer(RuntimeOrigin::signed(1), 1, 7, 2), Error::<Test>::OfferNotFound);
+		assert_noop!(Marketplace::accept_offer(RuntimeOrigin::signed(2), 1, 7, 3), Error::<Test>::NotItemOwner);
+		assert_ok!(Marketplace::accept_offer(RuntimeOrigin::signed(1), 1, 7, 3));
+		System::assert_last_event(
+			Event::Sold { game_id: 1, item_id: 7, seller: 1, buyer: 3, asset_id: 1, price: 300, royalty: 30 }.into(),
+		);
+		assert_eq!(CrossGameItemUsage::owner_of(1, 7), Some(3));
+		assert_eq!(tokens(1), 1_270);
+		assert_eq!(tokens(3), 700);
+		assert_eq!(tokens(10), 30);
+		assert_eq!(tokens(EscrowAccount::get()), 0);
+		assert!(Marketplace::offers((1, 7), 3).is_none());
+	});
+}
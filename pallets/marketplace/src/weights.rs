@@ -0,0 +1,184 @@
+//! Autogenerated weights for pallet_marketplace
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2024-04-12, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `<UNKNOWN>`, CPU: `<UNKNOWN>`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ../../target/release/node-template
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_marketplace
+// --extrinsic
+// *
+// --steps=50
+// --repeat=20
+// --wasm-execution=compiled
+// --output
+// pallets/marketplace/src/weights.rs
+// --template
+// ../../.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_marketplace.
+pub trait WeightInfo {
+	fn set_royalty() -> Weight;
+	fn list_item() -> Weight;
+	fn cancel_listing() -> Weight;
+	fn buy() -> Weight;
+	fn make_offer() -> Weight;
+	fn withdraw_offer() -> Weight;
+	fn accept_offer() -> Weight;
+}
+
+/// Weights for pallet_marketplace using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: Marketplace Royalties (r:0 w:1)
+	fn set_royalty() -> Weight {
+		Weight::from_parts(13_000_000, 3507)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: AssetModule Assets (r:1 w:0)
+	/// Storage: Marketplace Listings (r:0 w:1)
+	fn list_item() -> Weight {
+		Weight::from_parts(19_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Marketplace Listings (r:1 w:1)
+	fn cancel_listing() -> Weight {
+		Weight::from_parts(15_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Marketplace Listings (r:1 w:1)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: Marketplace Royalties (r:1 w:0)
+	/// Storage: AssetModule Balances (r:3 w:3)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn buy() -> Weight {
+		Weight::from_parts(68_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: AssetModule Assets (r:1 w:0)
+	/// Storage: Marketplace Offers (r:1 w:1)
+	/// Storage: AssetModule Balances (r:2 w:2)
+	fn make_offer() -> Weight {
+		Weight::from_parts(31_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Marketplace Offers (r:1 w:1)
+	/// Storage: AssetModule Balances (r:2 w:2)
+	fn withdraw_offer() -> Weight {
+		Weight::from_parts(25_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: Marketplace Offers (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: Marketplace Royalties (r:1 w:0)
+	/// Storage: AssetModule Balances (r:3 w:3)
+	/// Storage: Marketplace Listings (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn accept_offer() -> Weight {
+		Weight::from_parts(66_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: Marketplace Royalties (r:0 w:1)
+	fn set_royalty() -> Weight {
+		Weight::from_parts(13_000_000, 3507)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: AssetModule Assets (r:1 w:0)
+	/// Storage: Marketplace Listings (r:0 w:1)
+	fn list_item() -> Weight {
+		Weight::from_parts(19_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Marketplace Listings (r:1 w:1)
+	fn cancel_listing() -> Weight {
+		Weight::from_parts(15_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Marketplace Listings (r:1 w:1)
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: Marketplace Royalties (r:1 w:0)
+	/// Storage: AssetModule Balances (r:3 w:3)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn buy() -> Weight {
+		Weight::from_parts(68_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:0)
+	/// Storage: AssetModule Assets (r:1 w:0)
+	/// Storage: Marketplace Offers (r:1 w:1)
+	/// Storage: AssetModule Balances (r:2 w:2)
+	fn make_offer() -> Weight {
+		Weight::from_parts(31_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Marketplace Offers (r:1 w:1)
+	/// Storage: AssetModule Balances (r:2 w:2)
+	fn withdraw_offer() -> Weight {
+		Weight::from_parts(25_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: CrossGameItemUsage GameItems (r:1 w:1)
+	/// Storage: Marketplace Offers (r:1 w:1)
+	/// Storage: CrossGameItemUsage Rentals (r:1 w:0)
+	/// Storage: CrossGameItemUsage GameOwners (r:1 w:0)
+	/// Storage: Marketplace Royalties (r:1 w:0)
+	/// Storage: AssetModule Balances (r:3 w:3)
+	/// Storage: Marketplace Listings (r:0 w:1)
+	/// Storage: CrossGameItemUsage ItemsByOwner (r:0 w:2)
+	/// Storage: CrossGameItemUsage RentalListings (r:0 w:1)
+	/// Storage: CrossGameItemUsage Approvals (r:0 w:1)
+	fn accept_offer() -> Weight {
+		Weight::from_parts(66_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+}
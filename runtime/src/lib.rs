@@ -9,8 +9,8 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	traits::{AccountIdConversion, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -25,6 +25,7 @@ pub use frame_support::{
 		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
 		StorageInfo,
 	},
+	PalletId,
 	weights::{
 		constants::{
 			BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND,
@@ -39,44 +40,20 @@ pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{FixedPointNumber, FixedU128, Perbill, Percent, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
 
-/// Import the pallet_assets Pallet
-use frame_system::{self as system};
-use pallet_assets::{self as assets};
-use pallet_balances::{self as balances};
-
 impl pallet_assets::Config for Runtime {
-    type Event = Event;
-    type Balance = u128;
-    type AssetId = u32;
-    type Currency = pallet_balances::Pallet<Runtime>;
-    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
-    type AssetDeposit = u128;
-    type AssetAccountDeposit = u128;
-    type MetadataDepositBase = u128;
-    type MetadataDepositPerByte = u128;
-    type ApprovalDeposit = u128;
-    type StringLimit = u32;
-    type Freezer = ();
-    type Extra = ();
-    type WeightInfo = ();
-}
-impl asset::Config for Runtime {
-    type Event = Event;
-    type Balance = u128;
-    type AssetId = u32;
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = u32;
+	type Balance = Balance;
 }
 
-/// Import the pallet_interest Pallet
-pub use pallet_interest;
 /// Import the pallet_lending Pallet
 pub use pallet_lending;
 
-
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -238,43 +215,140 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
-// impl pallet_assets::Config for Runtime {
-//     type Event = Event;
-// }
-
 // impl pallet_interest::Config for Runtime {
 //     type Event = Event;
 // }
 
-// impl pallet_lending::Config for Runtime {
-//     type Event = Event;
-// }
+parameter_types! {
+	pub const LoanExtensionFee: Percent = Percent::from_percent(2);
+	pub const LiquidationPenalty: Permill = Permill::from_percent(5);
+	pub const LiquidationPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const StableRateRebalanceThreshold: Permill = Permill::from_percent(5);
+	pub const LendingRewardAssetId: u32 = 0;
+	pub const LendingRewardsPalletId: PalletId = PalletId(*b"lend/rwd");
+	pub LendingRewardAccount: AccountId = LendingRewardsPalletId::get().into_account_truncating();
+	pub const LendingInsuranceFactor: Permill = Permill::from_percent(10);
+	pub const LendingEscrowPalletId: PalletId = PalletId(*b"lend/esc");
+	pub LendingItemEscrowAccount: AccountId = LendingEscrowPalletId::get().into_account_truncating();
+	pub const LendingPoolPalletId: PalletId = PalletId(*b"lend/pol");
+	pub LendingPoolAccount: AccountId = LendingPoolPalletId::get().into_account_truncating();
+	pub const AuctionStartPremium: Permill = Permill::from_percent(20);
+	pub const AuctionPriceDecay: Permill = Permill::from_percent(1);
+}
 
-#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub enum Event {
-    System(system::Event<Runtime>),
-    Balances(pallet_balances::Event<Runtime>),
-    Timestamp(pallet_timestamp::Event<Runtime>),
-    Assets(pallet_assets::Event<Runtime>),
-    Interest(pallet_interest::Event<Runtime>),
-    Lending(pallet_lending::Event<Runtime>),
+/// Prices every asset at one unit until an oracle pallet is integrated.
+pub struct FlatPriceFeed;
+impl pallet_lending::PriceFeed<u32> for FlatPriceFeed {
+	fn get_price(_asset_id: &u32) -> Option<FixedU128> {
+		Some(FixedU128::from_u32(1))
+	}
+}
+
+/// Holds pledged item collateral in the cross-game item registry.
+pub struct GameItemCustody;
+impl pallet_lending::ItemCustody<AccountId, (u32, u32)> for GameItemCustody {
+	fn owner(item_id: &(u32, u32)) -> Option<AccountId> {
+		<CrossGameItemUsage as pallet_cross_game_item_usage::CrossGameItemUsage>::owner_of(item_id.0, item_id.1)
+	}
+
+	fn transfer(item_id: &(u32, u32), from: &AccountId, to: &AccountId) -> frame_support::dispatch::DispatchResult {
+		<CrossGameItemUsage as pallet_cross_game_item_usage::CrossGameItemUsage>::transfer_ownership(
+			item_id.0, item_id.1, from, to,
+		)
+	}
+}
+
+impl pallet_lending::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = u32;
+	type Balance = Balance;
+	type Moment = BlockNumber;
+	type ExtensionFee = LoanExtensionFee;
+	type PriceFeed = FlatPriceFeed;
+	type MarketAdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxMarketsPerPool = ConstU32<16>;
+	type MaxInstructionsPerBlock = ConstU32<20>;
+	type MaxInstructionsPerAccount = ConstU32<8>;
+	type LiquidationPenalty = LiquidationPenalty;
+	type MaxLiquidationsPerTx = ConstU32<16>;
+	type UnsignedInterval = ConstU32<5>;
+	type UnsignedPriority = LiquidationPriority;
+	type RebalanceThreshold = StableRateRebalanceThreshold;
+	type Assets = Assets;
+	type RewardAssetId = LendingRewardAssetId;
+	type RewardAccount = LendingRewardAccount;
+	type InsuranceFactor = LendingInsuranceFactor;
+	/// Game items are identified by game id and item id.
+	type ItemId = (u32, u32);
+	type Items = GameItemCustody;
+	type ItemEscrowAccount = LendingItemEscrowAccount;
+	type PoolAccount = LendingPoolAccount;
+	type AuctionStartPremium = AuctionStartPremium;
+	type AuctionPriceDecay = AuctionPriceDecay;
+	type GovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+}
+
+impl pallet_cross_game_item_usage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type GameItemId = u32;
+	type Currency = Balances;
+	type AssetId = u32;
+	type AssetBalance = Balance;
+	type Assets = Assets;
+	type MaxRentalsPerBlock = ConstU32<64>;
+	type MaxGameNameLength = ConstU32<64>;
+	type MaxAttributeKeyLength = ConstU32<32>;
+	type MaxAttributeStringLength = ConstU32<64>;
+	type MaxAttributes = ConstU32<16>;
+	type WeightInfo = pallet_cross_game_item_usage::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const MarketplacePalletId: PalletId = PalletId(*b"mrkt/esc");
+	pub MarketplaceEscrowAccount: AccountId = MarketplacePalletId::get().into_account_truncating();
+	pub const MarketplaceMaxRoyalty: Permill = Permill::from_percent(25);
+}
+
+/// Creates games and items directly in the cross-game item registry for marketplace benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MarketplaceBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_marketplace::BenchmarkHelper<AccountId, u32> for MarketplaceBenchmarkHelper {
+	fn create_game(game_id: u32, owner: &AccountId) {
+		let game_metadata = pallet_cross_game_item_usage::GameMetadata { name: Default::default() };
+		CrossGameItemUsage::register_game(RuntimeOrigin::signed(owner.clone()), game_id, game_metadata)
+			.expect("benchmark game id is free");
+	}
+
+	fn create_item(game_id: u32, item_id: u32, owner: &AccountId) {
+		let operator = pallet_cross_game_item_usage::GameOwners::<Runtime>::get(game_id).expect("game is created first");
+		CrossGameItemUsage::mint_item(RuntimeOrigin::signed(operator), game_id, item_id, 0, owner.clone(), Default::default())
+			.expect("benchmark item id is free");
+	}
+}
+
+impl pallet_marketplace::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type GameItemId = u32;
+	type AssetId = u32;
+	type Balance = Balance;
+	type Assets = Assets;
+	type Items = CrossGameItemUsage;
+	type EscrowAccount = MarketplaceEscrowAccount;
+	type MaxRoyalty = MarketplaceMaxRoyalty;
+	type WeightInfo = pallet_marketplace::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MarketplaceBenchmarkHelper;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = RuntimeCall;
 }
 
-// Construct the runtime by composing the FRAME pallets
-construct_runtime!(
-    pub enum Runtime where
-        Block = Block,
-        NodeBlock = Block,
-        UncheckedExtrinsic = UncheckedExtrinsic
-    {
-        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Event<T>, Config},
-        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>, Config},
-        Interest: pallet_interest::{Pallet, Call, Storage, Event<T>, Config},
-        Lending: pallet_lending::{Pallet, Call, Storage, Event<T>, Config},
-    }
-);
 /// Existential deposit.
 pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 
@@ -362,6 +436,18 @@ mod runtime {
 	// Include the custom logic from the pallet-template in the runtime.
 	#[runtime::pallet_index(7)]
 	pub type TemplateModule = pallet_template;
+
+	#[runtime::pallet_index(8)]
+	pub type Lending = pallet_lending;
+
+	#[runtime::pallet_index(9)]
+	pub type Assets = pallet_assets;
+
+	#[runtime::pallet_index(10)]
+	pub type CrossGameItemUsage = pallet_cross_game_item_usage;
+
+	#[runtime::pallet_index(11)]
+	pub type Marketplace = pallet_marketplace;
 }
 
 /// The address format for describing accounts.
@@ -409,9 +495,13 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
 		[pallet_template, TemplateModule]
+		[pallet_cross_game_item_usage, CrossGameItemUsage]
+		[pallet_marketplace, Marketplace]
+		[pallet_lending, Lending]
 	);
 }
 
@@ -583,6 +673,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_lending_runtime_api::LendingApi<Block, AccountId, u32, Balance> for Runtime {
+		fn account_liquidity(
+			pool_id: pallet_lending::PoolId,
+			who: AccountId,
+		) -> Option<pallet_lending::AccountLiquidity<Balance>> {
+			Lending::account_liquidity(pool_id, &who).ok()
+		}
+
+		fn health_factor(pool_id: pallet_lending::PoolId, who: AccountId) -> Option<FixedU128> {
+			Lending::health_factor(pool_id, &who).ok()
+		}
+
+		fn market_state(
+			pool_id: pallet_lending::PoolId,
+			asset_id: u32,
+		) -> Option<pallet_lending::Market<Balance>> {
+			Lending::markets(pool_id, asset_id)
+		}
+
+		fn supply_rate(pool_id: pallet_lending::PoolId, asset_id: u32) -> Permill {
+			Lending::supply_rate(pool_id, asset_id)
+		}
+
+		fn borrow_rate(pool_id: pallet_lending::PoolId, asset_id: u32) -> Permill {
+			Lending::borrow_rate(pool_id, asset_id)
+		}
+
+		fn accrued_yield(pool_id: pallet_lending::PoolId, who: AccountId, asset_id: u32) -> Balance {
+			Lending::accrued_yield(pool_id, &who, asset_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (